@@ -0,0 +1,102 @@
+//! Compares allocator traffic and run time of `LinkedList` and `PooledLinkedList` on a
+//! queue workload. Run with `cargo bench --bench pool`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use too_many_lists::sixth::{LinkedList, PooledLinkedList};
+
+struct Counting;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        DEALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const DEPTH: usize = 64;
+const OPS: usize = 1_000_000;
+
+trait Queue {
+    fn push_back(&mut self, elem: u64);
+    fn pop_front(&mut self) -> Option<u64>;
+}
+
+impl Queue for LinkedList<u64> {
+    fn push_back(&mut self, elem: u64) {
+        LinkedList::push_back(self, elem);
+    }
+
+    fn pop_front(&mut self) -> Option<u64> {
+        LinkedList::pop_front(self)
+    }
+}
+
+impl Queue for PooledLinkedList<u64> {
+    fn push_back(&mut self, elem: u64) {
+        PooledLinkedList::push_back(self, elem);
+    }
+
+    fn pop_front(&mut self) -> Option<u64> {
+        PooledLinkedList::pop_front(self)
+    }
+}
+
+/// Keeps `DEPTH` elements queued while pushing and popping `OPS` times.
+fn run<Q: Queue>(name: &str, new: impl FnOnce() -> Q) {
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let deallocs = DEALLOCS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let mut queue = new();
+
+    for i in 0..DEPTH as u64 {
+        queue.push_back(i);
+    }
+    let mut sum = 0u64;
+    for i in 0..OPS as u64 {
+        queue.push_back(i);
+        sum = sum.wrapping_add(queue.pop_front().unwrap());
+    }
+    black_box(sum);
+
+    let elapsed = start.elapsed();
+    drop(queue);
+    report(
+        name,
+        ALLOCS.load(Ordering::Relaxed) - allocs,
+        DEALLOCS.load(Ordering::Relaxed) - deallocs,
+        elapsed,
+    );
+}
+
+fn report(name: &str, allocs: usize, deallocs: usize, elapsed: Duration) {
+    println!(
+        "{name:<28} {allocs:>9} allocs {deallocs:>9} deallocs {:>8.2} ns/op",
+        elapsed.as_nanos() as f64 / OPS as f64
+    );
+}
+
+fn main() {
+    println!("queue of {DEPTH} u64s, {OPS} push_back/pop_front pairs");
+    run("LinkedList", LinkedList::new);
+    run("PooledLinkedList", PooledLinkedList::new);
+    run("PooledLinkedList (reserved)", || {
+        PooledLinkedList::with_node_cache(DEPTH + 1)
+    });
+}
@@ -0,0 +1,97 @@
+use std::{
+    alloc::{self, Layout},
+    error::Error,
+    fmt,
+};
+
+/// The error returned by the `try_*` insertion methods when a node can't be allocated.
+///
+/// It hands back the element that was being inserted.
+pub struct AllocError<T> {
+    elem: T,
+}
+
+impl<T> AllocError<T> {
+    pub(crate) fn new(elem: T) -> Self {
+        Self { elem }
+    }
+
+    /// Returns the element that couldn't be inserted.
+    pub fn into_inner(self) -> T {
+        self.elem
+    }
+}
+
+impl<T> fmt::Debug for AllocError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for AllocError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl<T> Error for AllocError<T> {}
+
+/// Like `Box::new`, but gives `value` back instead of aborting if the allocation fails.
+pub(crate) fn try_box<T>(value: T) -> Result<Box<T>, T> {
+    let layout = Layout::new::<T>();
+    if layout.size() == 0 {
+        return Ok(Box::new(value));
+    }
+    let ptr = unsafe { alloc::alloc(layout) }.cast::<T>();
+    if ptr.is_null() {
+        return Err(value);
+    }
+    unsafe {
+        ptr.write(value);
+        Ok(Box::from_raw(ptr))
+    }
+}
+
+/// A broken structural invariant, reported by [`LinkedList::validate`] and
+/// [`CursorMut::validate`].
+///
+/// [`LinkedList::validate`]: crate::sixth::LinkedList::validate
+/// [`CursorMut::validate`]: crate::sixth::CursorMut::validate
+#[cfg(feature = "debug-invariants")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvariantError {
+    /// The node at `index` doesn't link back to the node before it, or the front node has a
+    /// node before it.
+    BrokenLink { index: usize },
+    /// The list's back isn't the last node reached by walking from its front.
+    BackMismatch,
+    /// `len` disagrees with the number of nodes in the list.
+    LenMismatch { len: usize, actual: usize },
+    /// The cursor's node isn't part of its list.
+    CursorNotInList,
+    /// The cursor's `index` disagrees with where its node actually is.
+    CursorIndexMismatch { index: usize, actual: usize },
+}
+
+#[cfg(feature = "debug-invariants")]
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BrokenLink { index } => {
+                write!(f, "node {index} doesn't link back to the node before it")
+            }
+            Self::BackMismatch => f.write_str("list back isn't the last node"),
+            Self::LenMismatch { len, actual } => {
+                write!(f, "list len is {len} but it has {actual} nodes")
+            }
+            Self::CursorNotInList => f.write_str("cursor node isn't in its list"),
+            Self::CursorIndexMismatch { index, actual } => {
+                write!(f, "cursor index is {index} but its node is at {actual}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "debug-invariants")]
+impl Error for InvariantError {}
@@ -1,5 +1,7 @@
 use std::ptr;
 
+use crate::error::{try_box, AllocError};
+
 pub struct List<T> {
     head: Link<T>,
     tail: *mut Node<T>,
@@ -31,10 +33,25 @@ impl<T> List<T> {
     }
 
     pub fn push(&mut self, elem: T) {
-        let new_tail: *mut _ = Box::into_raw(Box::new(Node {
+        self.push_node(Box::new(Node {
             elem,
             next: ptr::null_mut(),
         }));
+    }
+
+    /// Like [`push`](Self::push), but hands `elem` back instead of aborting if allocation fails.
+    pub fn try_push(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let node = try_box(Node {
+            elem,
+            next: ptr::null_mut(),
+        })
+        .map_err(|node| AllocError::new(node.elem))?;
+        self.push_node(node);
+        Ok(())
+    }
+
+    fn push_node(&mut self, node: Box<Node<T>>) {
+        let new_tail = Box::into_raw(node);
 
         if !self.tail.is_null() {
             unsafe {
@@ -83,6 +100,12 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         while self.pop().is_some() {}
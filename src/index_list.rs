@@ -0,0 +1,966 @@
+//! A doubly linked list whose nodes live in a `Vec` slab and link to each other by index.
+//!
+//! Compared to [`sixth::LinkedList`](crate::sixth::LinkedList), nodes sit next to each other in
+//! memory, cloning copies the slab as-is without relinking or allocating per node, and elements
+//! can be referred to by [`Index`] handles that stay safe to hold: a handle checks which list it
+//! came from and its slot's generation, so it never resolves on another list, and once its
+//! element is removed it just stops resolving. A clone counts as a different list, so handles
+//! into the original don't resolve on it.
+//!
+//! Because every list owns its own slab, the cursor's `split_*` and `splice_*` move elements
+//! across one at a time, in time linear in how many move, and handles to moved elements stop
+//! resolving. The exception is splitting from the ghost, which hands over the whole list,
+//! handles included.
+
+use std::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const NIL: u32 = u32::MAX;
+
+pub struct IndexList<T> {
+    slots: Vec<Slot<T>>,
+    front: u32,
+    back: u32,
+    len: usize,
+    // head of the chain of vacant slots, threaded through `Entry::Vacant`
+    free: u32,
+    // generation given to slots that haven't been allocated before
+    base_generation: u32,
+    // identifies this list to `Index` handles
+    id: u64,
+}
+
+/// A handle to an element of an [`IndexList`], valid on that list until the element is removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: u32,
+    generation: u32,
+    list: u64,
+}
+
+#[derive(Clone)]
+struct Slot<T> {
+    generation: u32,
+    entry: Entry<T>,
+}
+
+#[derive(Clone)]
+enum Entry<T> {
+    Occupied { elem: T, front: u32, back: u32 },
+    Vacant { next_free: u32 },
+}
+
+pub struct IntoIter<T>(IndexList<T>);
+
+pub struct Iter<'a, T> {
+    slots: &'a [Slot<T>],
+    front: u32,
+    back: u32,
+    len: usize,
+}
+
+pub struct IterMut<'a, T> {
+    slots: *mut Slot<T>,
+    front: u32,
+    back: u32,
+    len: usize,
+    _type_data: PhantomData<&'a mut T>,
+}
+
+fn next_list_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+pub struct CursorMut<'a, T> {
+    cur: u32,
+    list: &'a mut IndexList<T>,
+    index: usize,
+}
+
+impl<T> IndexList<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            front: NIL,
+            back: NIL,
+            len: 0,
+            free: NIL,
+            base_generation: 0,
+            id: next_list_id(),
+        }
+    }
+
+    pub fn push_front(&mut self, elem: T) -> Index {
+        self.link_before(elem, self.front)
+    }
+
+    pub fn push_back(&mut self, elem: T) -> Index {
+        self.link_before(elem, NIL)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        (self.front != NIL).then(|| self.unlink(self.front))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        (self.back != NIL).then(|| self.unlink(self.back))
+    }
+
+    #[must_use]
+    pub fn front(&self) -> Option<&T> {
+        self.elem(self.front)
+    }
+
+    #[must_use]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.elem_mut(self.front)
+    }
+
+    #[must_use]
+    pub fn back(&self) -> Option<&T> {
+        self.elem(self.back)
+    }
+
+    #[must_use]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.elem_mut(self.back)
+    }
+
+    /// Returns the handle of the front element.
+    #[must_use]
+    pub fn front_index(&self) -> Option<Index> {
+        self.handle(self.front)
+    }
+
+    /// Returns the handle of the back element.
+    #[must_use]
+    pub fn back_index(&self) -> Option<Index> {
+        self.handle(self.back)
+    }
+
+    /// Returns the element behind `index`, or `None` if it has been removed.
+    #[must_use]
+    pub fn get(&self, index: Index) -> Option<&T> {
+        self.check(index).and_then(|slot| self.elem(slot))
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        self.check(index).and_then(|slot| self.elem_mut(slot))
+    }
+
+    #[must_use]
+    pub fn contains(&self, index: Index) -> bool {
+        self.check(index).is_some()
+    }
+
+    /// Removes the element behind `index` in O(1), or returns `None` if it is already gone.
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        self.check(index).map(|slot| self.unlink(slot))
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: &self.slots,
+            front: self.front,
+            back: self.back,
+            len: self.len,
+        }
+    }
+
+    #[must_use]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots.as_mut_ptr(),
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _type_data: PhantomData,
+        }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: NIL,
+            list: self,
+            index: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many slots the slab holds, occupied or not.
+    #[must_use]
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Removes every element. Handles to them stop resolving, but the slots are kept for reuse.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Rewrites the slab so the elements occupy its first `len` slots in list order, and
+    /// releases the rest.
+    ///
+    /// Every [`Index`] handed out before compacting is invalidated.
+    pub fn compact(&mut self) {
+        let generation = self
+            .slots
+            .iter()
+            .map(|slot| slot.generation)
+            .fold(self.base_generation, u32::max)
+            .saturating_add(1);
+
+        let mut old = mem::take(&mut self.slots);
+        let mut slots = Vec::with_capacity(self.len);
+        let mut cur = self.front;
+        while cur != NIL {
+            let Entry::Occupied { elem, back, .. } = mem::replace(
+                &mut old[cur as usize].entry,
+                Entry::Vacant { next_free: NIL },
+            ) else {
+                unreachable!("linked slots are occupied");
+            };
+            let slot = slots.len() as u32;
+            slots.push(Slot {
+                generation,
+                entry: Entry::Occupied {
+                    elem,
+                    front: slot.checked_sub(1).unwrap_or(NIL),
+                    back: if back == NIL { NIL } else { slot + 1 },
+                },
+            });
+            cur = back;
+        }
+
+        self.slots = slots;
+        self.front = if self.len == 0 { NIL } else { 0 };
+        self.back = if self.len == 0 {
+            NIL
+        } else {
+            self.len as u32 - 1
+        };
+        self.free = NIL;
+        self.base_generation = generation;
+        // once the generations saturate they no longer tell old slots from new ones
+        self.id = next_list_id();
+    }
+
+    fn check(&self, index: Index) -> Option<u32> {
+        if index.list != self.id {
+            return None;
+        }
+        let slot = self.slots.get(index.slot as usize)?;
+        (slot.generation == index.generation && matches!(slot.entry, Entry::Occupied { .. }))
+            .then_some(index.slot)
+    }
+
+    fn handle(&self, slot: u32) -> Option<Index> {
+        (slot != NIL).then(|| Index {
+            slot,
+            generation: self.slots[slot as usize].generation,
+            list: self.id,
+        })
+    }
+
+    fn elem(&self, slot: u32) -> Option<&T> {
+        match self.slots.get(slot as usize)?.entry {
+            Entry::Occupied { ref elem, .. } => Some(elem),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    fn elem_mut(&mut self, slot: u32) -> Option<&mut T> {
+        match self.slots.get_mut(slot as usize)?.entry {
+            Entry::Occupied { ref mut elem, .. } => Some(elem),
+            Entry::Vacant { .. } => None,
+        }
+    }
+
+    fn links(&self, slot: u32) -> (u32, u32) {
+        match self.slots[slot as usize].entry {
+            Entry::Occupied { front, back, .. } => (front, back),
+            Entry::Vacant { .. } => unreachable!("linked slots are occupied"),
+        }
+    }
+
+    fn links_mut(&mut self, slot: u32) -> (&mut u32, &mut u32) {
+        match self.slots[slot as usize].entry {
+            Entry::Occupied {
+                ref mut front,
+                ref mut back,
+                ..
+            } => (front, back),
+            Entry::Vacant { .. } => unreachable!("linked slots are occupied"),
+        }
+    }
+
+    /// Stores `elem` in a free slot and links it in before `next`, or at the back if `next`
+    /// is `NIL`.
+    fn link_before(&mut self, elem: T, next: u32) -> Index {
+        let prev = if next == NIL {
+            self.back
+        } else {
+            self.links(next).0
+        };
+        let entry = Entry::Occupied {
+            elem,
+            front: prev,
+            back: next,
+        };
+
+        let slot = if self.free != NIL {
+            let slot = self.free;
+            let Entry::Vacant { next_free } =
+                mem::replace(&mut self.slots[slot as usize].entry, entry)
+            else {
+                unreachable!("free slots are vacant");
+            };
+            self.free = next_free;
+            slot
+        } else {
+            let slot = self.slots.len();
+            assert!(
+                slot < NIL as usize,
+                "IndexList cannot hold more than u32::MAX - 1 slots"
+            );
+            self.slots.push(Slot {
+                generation: self.base_generation,
+                entry,
+            });
+            slot as u32
+        };
+
+        if prev == NIL {
+            self.front = slot;
+        } else {
+            *self.links_mut(prev).1 = slot;
+        }
+        if next == NIL {
+            self.back = slot;
+        } else {
+            *self.links_mut(next).0 = slot;
+        }
+        self.len += 1;
+
+        Index {
+            slot,
+            generation: self.slots[slot as usize].generation,
+            list: self.id,
+        }
+    }
+
+    /// Unlinks an occupied slot and puts it on the free chain, unless it is retired.
+    fn unlink(&mut self, slot: u32) -> T {
+        let (prev, next) = self.links(slot);
+        if prev == NIL {
+            self.front = next;
+        } else {
+            *self.links_mut(prev).1 = next;
+        }
+        if next == NIL {
+            self.back = prev;
+        } else {
+            *self.links_mut(next).0 = prev;
+        }
+        self.len -= 1;
+
+        // a slot whose generation can't go any higher is retired rather than reused, so that
+        // stale handles to it can never match again
+        let freed = &mut self.slots[slot as usize];
+        let retire = freed.generation == u32::MAX;
+        let next_free = if retire { NIL } else { self.free };
+        let Entry::Occupied { elem, .. } =
+            mem::replace(&mut freed.entry, Entry::Vacant { next_free })
+        else {
+            unreachable!("linked slots are occupied");
+        };
+        if !retire {
+            freed.generation += 1;
+            self.free = slot;
+        }
+        elem
+    }
+}
+
+impl<T: Clone> Clone for IndexList<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            free: self.free,
+            base_generation: self.base_generation,
+            id: next_list_id(),
+        }
+    }
+}
+
+impl<T> Default for IndexList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Extend<T> for IndexList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for IndexList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug> Debug for IndexList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for IndexList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for IndexList<T> {}
+
+impl<T> IntoIterator for IndexList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IndexList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut IndexList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let Entry::Occupied { elem, back, .. } = &self.slots[self.front as usize].entry else {
+            unreachable!("linked slots are occupied");
+        };
+        self.len -= 1;
+        self.front = *back;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let Entry::Occupied { elem, front, .. } = &self.slots[self.back as usize].entry else {
+            unreachable!("linked slots are occupied");
+        };
+        self.len -= 1;
+        self.back = *front;
+        Some(elem)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // each slot is visited once, so the mutable borrows never overlap
+        let Entry::Occupied { elem, back, .. } =
+            (unsafe { &mut (*self.slots.add(self.front as usize)).entry })
+        else {
+            unreachable!("linked slots are occupied");
+        };
+        self.len -= 1;
+        self.front = *back;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let Entry::Occupied { elem, front, .. } =
+            (unsafe { &mut (*self.slots.add(self.back as usize)).entry })
+        else {
+            unreachable!("linked slots are occupied");
+        };
+        self.len -= 1;
+        self.back = *front;
+        Some(elem)
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<T> CursorMut<'_, T> {
+    pub fn index(&self) -> Option<usize> {
+        (self.cur != NIL).then_some(self.index)
+    }
+
+    /// Returns the handle of the current element.
+    pub fn handle(&self) -> Option<Index> {
+        self.list.handle(self.cur)
+    }
+
+    pub fn move_next(&mut self) {
+        if self.cur != NIL {
+            self.cur = self.list.links(self.cur).1;
+            self.index += 1;
+        } else {
+            self.cur = self.list.front;
+            self.index = 0;
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if self.cur != NIL {
+            self.cur = self.list.links(self.cur).0;
+            self.index = self.index.wrapping_sub(1);
+        } else {
+            self.cur = self.list.back;
+            self.index = self.list.len.wrapping_sub(1);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.elem_mut(self.cur)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = if self.cur != NIL {
+            self.list.links(self.cur).1
+        } else {
+            self.list.front
+        };
+        self.list.elem_mut(next)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = if self.cur != NIL {
+            self.list.links(self.cur).0
+        } else {
+            self.list.back
+        };
+        self.list.elem_mut(prev)
+    }
+
+    /// Inserts `elem` before the current element, or at the back if on the ghost.
+    pub fn insert_before(&mut self, elem: T) -> Index {
+        let index = self.list.link_before(elem, self.cur);
+        if self.cur != NIL {
+            self.index += 1;
+        }
+        index
+    }
+
+    /// Inserts `elem` after the current element, or at the front if on the ghost.
+    pub fn insert_after(&mut self, elem: T) -> Index {
+        let next = if self.cur != NIL {
+            self.list.links(self.cur).1
+        } else {
+            self.list.front
+        };
+        self.list.link_before(elem, next)
+    }
+
+    /// Removes the current element and moves the cursor on to the next one.
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.cur == NIL {
+            return None;
+        }
+        let cur = self.cur;
+        self.cur = self.list.links(cur).1;
+        Some(self.list.unlink(cur))
+    }
+
+    pub fn split_before(&mut self) -> IndexList<T> {
+        if self.cur == NIL {
+            return mem::take(self.list);
+        }
+        let mut split = IndexList::with_capacity(self.index);
+        while self.list.front != self.cur {
+            split.push_back(self.list.unlink(self.list.front));
+        }
+        self.index = 0;
+        split
+    }
+
+    pub fn split_after(&mut self) -> IndexList<T> {
+        if self.cur == NIL {
+            return mem::take(self.list);
+        }
+        let mut split = IndexList::with_capacity(self.list.len - self.index - 1);
+        while self.list.back != self.cur {
+            split.push_front(self.list.unlink(self.list.back));
+        }
+        split
+    }
+
+    /// Moves the elements of `other` in before the current element, or at the back if on the
+    /// ghost.
+    pub fn splice_before(&mut self, other: IndexList<T>) {
+        for elem in other {
+            self.insert_before(elem);
+        }
+    }
+
+    /// Moves the elements of `other` in after the current element, or at the front if on the
+    /// ghost.
+    pub fn splice_after(&mut self, other: IndexList<T>) {
+        for elem in other.into_iter().rev() {
+            self.insert_after(elem);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexList;
+
+    fn contents<T: Clone>(list: &IndexList<T>) -> Vec<T> {
+        let forwards: Vec<_> = list.iter().cloned().collect();
+        let mut backwards: Vec<_> = list.iter().rev().cloned().collect();
+        backwards.reverse();
+        assert_eq!(forwards.len(), list.len());
+        assert_eq!(backwards.len(), list.len());
+        forwards
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut list = IndexList::new();
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list.len(), 3);
+        assert_eq!(contents(&list), &[1, 2, 3]);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+        *list.front_mut().unwrap() = 10;
+        *list.back_mut().unwrap() = 30;
+
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.pop_back(), Some(30));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+
+        // vacant slots are reused before the slab grows
+        list.extend([4, 5, 6]);
+        assert_eq!(list.slot_count(), 3);
+        assert_eq!(contents(&list), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn handles() {
+        let mut list = IndexList::new();
+        let a = list.push_back('a');
+        let b = list.push_back('b');
+        let c = list.push_front('c');
+        assert_eq!(list.get(a), Some(&'a'));
+        assert_eq!(list.front_index(), Some(c));
+        assert_eq!(list.back_index(), Some(b));
+        *list.get_mut(b).unwrap() = 'B';
+
+        assert_eq!(list.remove(a), Some('a'));
+        assert_eq!(list.remove(a), None);
+        assert!(!list.contains(a));
+        assert_eq!(contents(&list), &['c', 'B']);
+
+        // the freed slot is reused, but the old handle still doesn't resolve
+        let d = list.push_back('d');
+        assert_eq!(list.get(a), None);
+        assert_eq!(list.get(d), Some(&'d'));
+
+        list.clear();
+        assert_eq!(list.get(b), None);
+        assert_eq!(list.get(d), None);
+        assert_eq!(list.front_index(), None);
+    }
+
+    #[test]
+    fn handles_belong_to_their_list() {
+        let mut a = IndexList::new();
+        let mut b = IndexList::new();
+        let in_a = a.push_back("only-in-a");
+        let in_b = b.push_back("b0");
+
+        assert_eq!(b.get(in_a), None);
+        assert_eq!(b.get_mut(in_a), None);
+        assert!(!b.contains(in_a));
+        assert_eq!(b.remove(in_a), None);
+        assert_eq!(a.remove(in_b), None);
+        assert_eq!(contents(&a), &["only-in-a"]);
+        assert_eq!(contents(&b), &["b0"]);
+    }
+
+    #[test]
+    fn clone_copies_slab() {
+        let mut list: IndexList<_> = (0..5).collect();
+        let two = list.iter().position(|&x| x == 2).unwrap();
+        let mut cursor = list.cursor_mut();
+        for _ in 0..=two {
+            cursor.move_next();
+        }
+        let handle = cursor.handle().unwrap();
+
+        let mut copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(copy.get(handle), None);
+        assert_eq!(copy.remove(handle), None);
+        assert_eq!(list.get(handle), Some(&2));
+
+        let mut cursor = copy.cursor_mut();
+        for _ in 0..=two {
+            cursor.move_next();
+        }
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(contents(&copy), &[0, 1, 3, 4]);
+        assert_eq!(contents(&list), &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn clone_issues_its_own_handles() {
+        let mut orig = IndexList::new();
+        orig.push_back("shared");
+        let mut copy = orig.clone();
+        let h = copy.push_back("only-in-copy");
+        orig.push_back("only-in-orig");
+        assert_eq!(orig.get(h), None);
+        assert_eq!(orig.remove(h), None);
+        assert_eq!(copy.get(h), Some(&"only-in-copy"));
+        assert_eq!(contents(&orig), &["shared", "only-in-orig"]);
+    }
+
+    #[test]
+    fn compact() {
+        let mut list = IndexList::new();
+        let handles: Vec<_> = (0..10).map(|i| list.push_front(i)).collect();
+        for &handle in handles.iter().step_by(3) {
+            list.remove(handle);
+        }
+        assert_eq!(contents(&list), &[8, 7, 5, 4, 2, 1]);
+        assert_eq!(list.slot_count(), 10);
+
+        list.compact();
+        assert_eq!(list.slot_count(), 6);
+        assert_eq!(contents(&list), &[8, 7, 5, 4, 2, 1]);
+        assert!(handles.iter().all(|&handle| list.get(handle).is_none()));
+
+        let new = list.push_back(0);
+        assert_eq!(list.get(new), Some(&0));
+        assert!(handles.iter().all(|&handle| list.get(handle).is_none()));
+        assert_eq!(list.pop_front(), Some(8));
+        assert_eq!(contents(&list), &[7, 5, 4, 2, 1, 0]);
+
+        let mut empty: IndexList<i32> = IndexList::new();
+        empty.compact();
+        assert!(empty.is_empty());
+        empty.push_back(1);
+        assert_eq!(contents(&empty), &[1]);
+    }
+
+    #[test]
+    fn exhausted_slots_are_retired() {
+        let mut list = IndexList::new();
+        list.push_back(0);
+        list.slots[0].generation = u32::MAX - 1;
+        let first = list.front_index().unwrap();
+        assert_eq!(list.remove(first), Some(0));
+
+        let last = list.push_back(1);
+        assert_eq!(list.slot_count(), 1);
+        assert_eq!(list.remove(last), Some(1));
+        list.push_back(2);
+        assert_eq!(list.slot_count(), 2);
+        assert_eq!(list.get(first), None);
+        assert_eq!(list.get(last), None);
+        assert_eq!(contents(&list), &[2]);
+
+        // compacting at the top generation still invalidates old handles
+        list.base_generation = u32::MAX;
+        let saturated = list.push_front(3);
+        list.compact();
+        assert_eq!(list.slots[0].generation, u32::MAX);
+        assert_eq!(list.get(saturated), None);
+        assert_eq!(contents(&list), &[3, 2]);
+    }
+
+    #[test]
+    fn cursor_split_splice() {
+        let mut list: IndexList<_> = (0..6).collect();
+        let mut cursor = list.cursor_mut();
+        for _ in 0..4 {
+            cursor.move_next();
+        }
+        let three = cursor.handle().unwrap();
+        cursor.move_prev();
+        let two = cursor.handle().unwrap();
+
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(contents(&before), &[0, 1]);
+        let after = cursor.split_after();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(contents(&after), &[3, 4, 5]);
+        assert_eq!(after.get(three), None);
+
+        // splicing back in, in the opposite places
+        cursor.splice_before(after);
+        assert_eq!(cursor.index(), Some(3));
+        cursor.splice_after(before);
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(contents(&list), &[3, 4, 5, 2, 0, 1]);
+        assert_eq!(list.get(two), Some(&2));
+        assert_eq!(list.get(three), None);
+
+        // from the ghost, splits take and splices add at the ends
+        let mut cursor = list.cursor_mut();
+        cursor.splice_after((10..12).collect());
+        cursor.splice_before((20..22).collect());
+        assert_eq!(cursor.index(), None);
+        let all = cursor.split_after();
+        assert!(list.is_empty());
+        assert_eq!(all.get(two), Some(&2));
+        assert_eq!(contents(&all), &[10, 11, 3, 4, 5, 2, 0, 1, 20, 21]);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list: IndexList<_> = (0..4).collect();
+        for elem in &mut list {
+            *elem *= 10;
+        }
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(&mut 30));
+        assert_eq!(iter.next(), Some(&mut 0));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), &[30, 20, 10, 0]);
+    }
+
+    #[test]
+    fn cursor() {
+        let mut list: IndexList<_> = (1..=4).collect();
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 4));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        let before = cursor.insert_before(10);
+        assert_eq!(cursor.index(), Some(2));
+        let after = cursor.insert_after(20);
+        assert_eq!(cursor.peek_next(), Some(&mut 20));
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 20));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_after(0);
+        cursor.insert_before(5);
+        assert_eq!(list.get(before), Some(&10));
+        assert_eq!(list.get(after), Some(&20));
+        assert_eq!(contents(&list), &[0, 1, 10, 20, 3, 4, 5]);
+    }
+}
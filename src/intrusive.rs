@@ -0,0 +1,552 @@
+//! An intrusive doubly linked list over user-owned, pinned nodes.
+//!
+//! Instead of allocating a node per element, the element type embeds one [`Links`] field per
+//! list it can be on, and an [`Adapter`] tells a [`List`] which field to use. Linking and
+//! unlinking never allocate, and an element can sit on as many lists as it has `Links` fields.
+//!
+//! Nodes are borrowed as `Pin<&'a T>` for as long as the list exists, so they can neither move
+//! nor be dropped while linked.
+
+use std::{
+    cell::Cell,
+    fmt::{self, Debug},
+    marker::{PhantomData, PhantomPinned},
+    pin::Pin,
+    ptr::NonNull,
+};
+
+/// The link field an element embeds for each list it can be on.
+pub struct Links<T> {
+    front: Cell<Option<NonNull<T>>>,
+    back: Cell<Option<NonNull<T>>>,
+    linked: Cell<bool>,
+    _pin: PhantomPinned,
+}
+
+/// Picks the [`Links`] field of `Target` that a [`List`] uses.
+///
+/// Use [`intrusive_adapter!`](crate::intrusive_adapter) to implement this for a field.
+///
+/// # Safety
+///
+/// `links` must always return the same field of `target` itself, never a `Links` stored
+/// anywhere else.
+pub unsafe trait Adapter {
+    type Target;
+
+    fn links(target: &Self::Target) -> &Links<Self::Target>;
+}
+
+/// Declares a unit struct implementing [`Adapter`] for one `Links` field of a type.
+///
+/// ```
+/// use too_many_lists::{intrusive::Links, intrusive_adapter};
+///
+/// struct Task {
+///     id: u32,
+///     ready: Links<Task>,
+/// }
+///
+/// intrusive_adapter!(ReadyAdapter = Task { ready });
+/// ```
+#[macro_export]
+macro_rules! intrusive_adapter {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $target:ty { $field:ident }) => {
+        $(#[$attr])*
+        $vis struct $name;
+
+        unsafe impl $crate::intrusive::Adapter for $name {
+            type Target = $target;
+
+            fn links(target: &$target) -> &$crate::intrusive::Links<$target> {
+                &target.$field
+            }
+        }
+    };
+}
+
+pub struct List<'a, A: Adapter> {
+    front: Link<A::Target>,
+    back: Link<A::Target>,
+    len: usize,
+    _type_data: PhantomData<&'a A::Target>,
+    _adapter: PhantomData<fn() -> A>,
+}
+
+type Link<T> = Option<NonNull<T>>;
+
+pub struct Iter<'l, 'a, A: Adapter> {
+    front: Link<A::Target>,
+    back: Link<A::Target>,
+    len: usize,
+    _type_data: PhantomData<&'l List<'a, A>>,
+}
+
+pub struct CursorMut<'l, 'a, A: Adapter> {
+    cur: Link<A::Target>,
+    list: &'l mut List<'a, A>,
+    index: usize,
+}
+
+impl<T> Links<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            front: Cell::new(None),
+            back: Cell::new(None),
+            linked: Cell::new(false),
+            _pin: PhantomPinned,
+        }
+    }
+
+    #[must_use]
+    pub fn is_linked(&self) -> bool {
+        self.linked.get()
+    }
+}
+
+impl<T> Default for Links<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for Links<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Links")
+            .field("linked", &self.is_linked())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, A: Adapter> List<'a, A> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            front: None,
+            back: None,
+            len: 0,
+            _type_data: PhantomData,
+            _adapter: PhantomData,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `node` is already linked through this adapter's field.
+    pub fn push_front(&mut self, node: Pin<&'a A::Target>) {
+        self.link_before(node, self.front);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `node` is already linked through this adapter's field.
+    pub fn push_back(&mut self, node: Pin<&'a A::Target>) {
+        self.link_before(node, None);
+    }
+
+    pub fn pop_front(&mut self) -> Option<Pin<&'a A::Target>> {
+        self.front.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<Pin<&'a A::Target>> {
+        self.back.map(|node| unsafe { self.unlink(node) })
+    }
+
+    #[must_use]
+    pub fn front(&self) -> Option<Pin<&'a A::Target>> {
+        self.front.map(|node| unsafe { Self::pinned(node) })
+    }
+
+    #[must_use]
+    pub fn back(&self) -> Option<Pin<&'a A::Target>> {
+        self.back.map(|node| unsafe { Self::pinned(node) })
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Unlinks every node, leaving them free to be linked into another list.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, 'a, A> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _type_data: PhantomData,
+        }
+    }
+
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, 'a, A> {
+        CursorMut {
+            cur: None,
+            list: self,
+            index: 0,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `node` must have been linked into a list of lifetime `'a`.
+    unsafe fn links(node: NonNull<A::Target>) -> &'a Links<A::Target> {
+        A::links(&*node.as_ptr())
+    }
+
+    /// # Safety
+    ///
+    /// `node` must have been linked into a list of lifetime `'a`.
+    unsafe fn pinned(node: NonNull<A::Target>) -> Pin<&'a A::Target> {
+        // nodes only get in through `Pin<&'a A::Target>`
+        Pin::new_unchecked(&*node.as_ptr())
+    }
+
+    /// Links `node` in before `next`, or at the back if `next` is `None`.
+    fn link_before(&mut self, node: Pin<&'a A::Target>, next: Link<A::Target>) {
+        let target = node.get_ref();
+        let links = A::links(target);
+        assert!(
+            !links.linked.replace(true),
+            "node is already linked into a list"
+        );
+        let node = NonNull::from(target);
+        unsafe {
+            let prev = match next {
+                Some(next) => Self::links(next).front.replace(Some(node)),
+                None => self.back.replace(node),
+            };
+            match prev {
+                Some(prev) => Self::links(prev).back.set(Some(node)),
+                None => self.front = Some(node),
+            }
+            links.front.set(prev);
+            links.back.set(next);
+        }
+        self.len += 1;
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be linked into this list.
+    unsafe fn unlink(&mut self, node: NonNull<A::Target>) -> Pin<&'a A::Target> {
+        let links = Self::links(node);
+        let prev = links.front.take();
+        let next = links.back.take();
+        match prev {
+            Some(prev) => Self::links(prev).back.set(next),
+            None => self.front = next,
+        }
+        match next {
+            Some(next) => Self::links(next).front.set(prev),
+            None => self.back = prev,
+        }
+        links.linked.set(false);
+        self.len -= 1;
+        Self::pinned(node)
+    }
+}
+
+impl<A: Adapter> Default for List<'_, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Adapter> Drop for List<'_, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<A: Adapter> Debug for List<'_, A>
+where
+    A::Target: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'l, 'a, A: Adapter> IntoIterator for &'l List<'a, A> {
+    type Item = Pin<&'a A::Target>;
+    type IntoIter = Iter<'l, 'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A: Adapter> Iterator for Iter<'_, 'a, A> {
+    type Item = Pin<&'a A::Target>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = List::<A>::links(node).back.get();
+                List::<A>::pinned(node)
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<A: Adapter> DoubleEndedIterator for Iter<'_, '_, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = List::<A>::links(node).front.get();
+                List::<A>::pinned(node)
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<A: Adapter> ExactSizeIterator for Iter<'_, '_, A> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, A: Adapter> CursorMut<'_, 'a, A> {
+    pub fn index(&self) -> Option<usize> {
+        self.cur?;
+        Some(self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur.take() {
+            self.cur = unsafe { List::<A>::links(cur).back.get() };
+            self.index += 1;
+        } else {
+            self.cur = self.list.front;
+            self.index = 0;
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur.take() {
+            self.cur = unsafe { List::<A>::links(cur).front.get() };
+            self.index = self.index.wrapping_sub(1);
+        } else {
+            self.cur = self.list.back;
+            self.index = self.list.len.wrapping_sub(1);
+        }
+    }
+
+    pub fn current(&self) -> Option<Pin<&'a A::Target>> {
+        self.cur.map(|node| unsafe { List::<A>::pinned(node) })
+    }
+
+    pub fn peek_next(&self) -> Option<Pin<&'a A::Target>> {
+        unsafe {
+            match self.cur {
+                Some(cur) => List::<A>::links(cur).back.get(),
+                None => self.list.front,
+            }
+            .map(|node| List::<A>::pinned(node))
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<Pin<&'a A::Target>> {
+        unsafe {
+            match self.cur {
+                Some(cur) => List::<A>::links(cur).front.get(),
+                None => self.list.back,
+            }
+            .map(|node| List::<A>::pinned(node))
+        }
+    }
+
+    /// Links `node` in before the current element, or at the back if on the ghost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is already linked through this adapter's field.
+    pub fn insert_before(&mut self, node: Pin<&'a A::Target>) {
+        self.list.link_before(node, self.cur);
+        if self.cur.is_some() {
+            self.index += 1;
+        }
+    }
+
+    /// Links `node` in after the current element, or at the front if on the ghost.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node` is already linked through this adapter's field.
+    pub fn insert_after(&mut self, node: Pin<&'a A::Target>) {
+        let next = match self.cur {
+            Some(cur) => unsafe { List::<A>::links(cur).back.get() },
+            None => self.list.front,
+        };
+        self.list.link_before(node, next);
+    }
+
+    /// Unlinks the current element and moves the cursor on to the next one.
+    pub fn remove_current(&mut self) -> Option<Pin<&'a A::Target>> {
+        self.cur.map(|cur| unsafe {
+            self.cur = List::<A>::links(cur).back.get();
+            self.list.unlink(cur)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+
+    use super::{Links, List};
+
+    struct Task {
+        id: u32,
+        ready: Links<Task>,
+        all: Links<Task>,
+    }
+
+    impl Task {
+        fn new(id: u32) -> Self {
+            Task {
+                id,
+                ready: Links::new(),
+                all: Links::new(),
+            }
+        }
+    }
+
+    crate::intrusive_adapter!(ReadyAdapter = Task { ready });
+    crate::intrusive_adapter!(AllAdapter = Task { all });
+
+    fn ids<'a>(list: &List<'a, impl super::Adapter<Target = Task>>) -> Vec<u32> {
+        list.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn push_pop() {
+        let a = pin!(Task::new(1));
+        let b = pin!(Task::new(2));
+        let c = pin!(Task::new(3));
+        let (a, b, c) = (a.into_ref(), b.into_ref(), c.into_ref());
+
+        let mut list = List::<ReadyAdapter>::new();
+        assert_eq!(list.pop_front().map(|t| t.id), None);
+        list.push_back(b);
+        list.push_front(a);
+        list.push_back(c);
+        assert_eq!(list.len(), 3);
+        assert!(b.ready.is_linked());
+        assert!(!b.all.is_linked());
+        assert_eq!(ids(&list), &[1, 2, 3]);
+        assert_eq!(
+            list.iter().rev().map(|t| t.id).collect::<Vec<_>>(),
+            &[3, 2, 1]
+        );
+        assert_eq!(list.front().map(|t| t.id), Some(1));
+        assert_eq!(list.back().map(|t| t.id), Some(3));
+
+        assert_eq!(list.pop_back().map(|t| t.id), Some(3));
+        assert!(!c.ready.is_linked());
+        assert_eq!(list.pop_front().map(|t| t.id), Some(1));
+        assert_eq!(list.pop_front().map(|t| t.id), Some(2));
+        assert_eq!(list.pop_back().map(|t| t.id), None);
+        assert!(list.is_empty());
+
+        list.push_back(c);
+        list.push_back(a);
+        assert_eq!(ids(&list), &[3, 1]);
+    }
+
+    #[test]
+    fn multiple_lists() {
+        let tasks: Vec<_> = (0..6).map(|id| Box::pin(Task::new(id))).collect();
+        let mut all = List::<AllAdapter>::new();
+        let mut ready = List::<ReadyAdapter>::new();
+        for task in &tasks {
+            all.push_back(task.as_ref());
+            if task.id % 2 == 0 {
+                ready.push_front(task.as_ref());
+            }
+        }
+        assert_eq!(ids(&all), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(ids(&ready), &[4, 2, 0]);
+
+        let first = ready.pop_back().unwrap();
+        assert_eq!(first.id, 0);
+        assert!(first.all.is_linked());
+        assert!(!first.ready.is_linked());
+        assert_eq!(all.len(), 6);
+
+        drop(ready);
+        assert!(tasks.iter().all(|task| !task.ready.is_linked()));
+        all.clear();
+        assert!(tasks.iter().all(|task| !task.all.is_linked()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn double_link() {
+        let a = pin!(Task::new(1));
+        let a = a.into_ref();
+        let mut list = List::<ReadyAdapter>::new();
+        let mut other = List::<ReadyAdapter>::new();
+        list.push_back(a);
+        other.push_back(a);
+    }
+
+    #[test]
+    fn cursor() {
+        let tasks: Vec<_> = (0..5).map(|id| Box::pin(Task::new(id))).collect();
+        let extra = Box::pin(Task::new(10));
+        let mut list = List::<AllAdapter>::new();
+        for task in &tasks {
+            list.push_back(task.as_ref());
+        }
+
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.current().map(|t| t.id), None);
+        assert_eq!(cursor.peek_next().map(|t| t.id), Some(0));
+        assert_eq!(cursor.peek_prev().map(|t| t.id), Some(4));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.remove_current().map(|t| t.id), Some(1));
+        assert_eq!(cursor.current().map(|t| t.id), Some(2));
+        assert_eq!(cursor.index(), Some(1));
+        cursor.insert_before(extra.as_ref());
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_after(tasks[1].as_ref());
+        cursor.move_prev();
+        assert_eq!(cursor.current().map(|t| t.id), Some(10));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current().map(|t| t.id), None);
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current().map(|t| t.id), Some(4));
+        assert_eq!(cursor.index(), None);
+
+        assert_eq!(ids(&list), &[0, 10, 2, 1, 3]);
+        assert_eq!(
+            list.iter().rev().map(|t| t.id).collect::<Vec<_>>(),
+            &[3, 1, 2, 10, 0]
+        );
+        assert!(!tasks[4].all.is_linked());
+    }
+}
@@ -1,6 +1,10 @@
+pub mod error;
 pub mod fifth;
 pub mod first;
 pub mod fourth;
+pub mod index_list;
+pub mod intrusive;
+pub mod lru;
 pub mod second;
 pub mod sixth;
 pub mod third;
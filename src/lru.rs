@@ -0,0 +1,361 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::{self, Debug},
+    hash::Hash,
+};
+
+use crate::sixth::{LinkedList, NodeHandle};
+
+/// A least-recently-used cache built on [`sixth::LinkedList`](crate::sixth::LinkedList).
+///
+/// Entries live in the list from most to least recently used, and the map points straight at
+/// their nodes, so lookups, insertions and evictions are all O(1).
+pub struct LruCache<K, V> {
+    map: HashMap<K, NodeHandle<(K, V)>>,
+    list: LinkedList<(K, V)>,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V) + Send>>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::with_capacity(capacity),
+            list: LinkedList::new(),
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Creates a cache that hands every entry it evicts to `on_evict`.
+    ///
+    /// The callback runs for entries pushed out by [`put`](Self::put) or
+    /// [`resize`](Self::resize), not for ones taken out explicitly.
+    #[must_use]
+    pub fn with_eviction_callback<F: FnMut(K, V) + Send + 'static>(
+        capacity: usize,
+        on_evict: F,
+    ) -> Self {
+        Self {
+            on_evict: Some(Box::new(on_evict)),
+            ..Self::new(capacity)
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the value for `key` and marks it as the most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.map.get(key)?;
+        self.list.move_to_front(handle);
+        self.list.get_handle_mut(handle).map(|(_, value)| value)
+    }
+
+    /// Returns the value for `key` without changing how recently it was used.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.map.get(key)?;
+        self.list.get_handle(handle).map(|(_, value)| value)
+    }
+
+    /// Returns the least recently used entry without changing how recently it was used.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.list.back().map(|(key, value)| (key, value))
+    }
+
+    /// Inserts `value` for `key` as the most recently used entry, returning the old value if
+    /// there was one.
+    ///
+    /// If this pushes the cache over capacity, the least recently used entry is evicted.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(handle) = self.map.get(&key) {
+            self.list.move_to_front(handle);
+            let (_, old) = self
+                .list
+                .get_handle_mut(handle)
+                .expect("cache handles always point into the cache's list");
+            return Some(std::mem::replace(old, value));
+        }
+
+        let handle = self.list.push_front_handle((key.clone(), value));
+        self.map.insert(key, handle);
+        self.evict_to(self.capacity);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.map.remove(key)?;
+        self.list.remove_handle(&handle).map(|(_, value)| value)
+    }
+
+    /// Removes and returns the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.list.pop_back()?;
+        self.map.remove(&key);
+        Some((key, value))
+    }
+
+    /// Changes the capacity, evicting least recently used entries if the cache is now too big.
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_to(capacity);
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.list.clear();
+    }
+
+    /// Iterates over the entries from most to least recently used.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator {
+        self.list.iter().map(|(key, value)| (key, value))
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.list.len() > capacity {
+            let Some((key, value)) = self.pop_lru() else {
+                break;
+            };
+            if let Some(on_evict) = &mut self.on_evict {
+                on_evict(key, value);
+            }
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.list.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::LruCache;
+
+    #[test]
+    fn send() {
+        fn is_send<T: Send>() {}
+        is_send::<LruCache<i32, String>>();
+
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let log = evicted.clone();
+        let mut cache = LruCache::with_eviction_callback(1, move |key, value| {
+            log.lock().unwrap().push((key, value));
+        });
+        cache.put(0, 0);
+        let cache = std::thread::spawn(move || {
+            cache.put(1, 10);
+            cache
+        })
+        .join()
+        .unwrap();
+        assert_eq!(cache.peek_lru(), Some((&1, &10)));
+        assert_eq!(*evicted.lock().unwrap(), &[(0, 0)]);
+    }
+
+    #[test]
+    fn basics() {
+        let mut cache = LruCache::new(2);
+        assert!(cache.is_empty());
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.put("c", 3), None);
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains("b"));
+        assert_eq!(cache.peek("a"), Some(&1));
+        assert_eq!(cache.peek_lru(), Some((&"a", &1)));
+
+        assert_eq!(cache.put("c", 30), Some(3));
+        assert_eq!(cache.pop_lru(), Some(("a", 1)));
+        assert_eq!(cache.get("a"), None);
+        *cache.get_mut("c").unwrap() += 1;
+        assert_eq!(cache.remove("c"), Some(31));
+        assert_eq!(cache.remove("c"), None);
+        assert!(cache.is_empty());
+        assert_eq!(cache.pop_lru(), None);
+    }
+
+    #[test]
+    fn peek_does_not_promote() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.peek(&1), Some(&"one"));
+        cache.put(3, "three");
+        assert!(!cache.contains(&1));
+        assert_eq!(
+            cache.iter().collect::<Vec<_>>(),
+            &[(&3, &"three"), (&2, &"two")]
+        );
+    }
+
+    #[test]
+    fn eviction_callback() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let log = evicted.clone();
+        let mut cache = LruCache::with_eviction_callback(3, move |key, value| {
+            log.lock().unwrap().push((key, value));
+        });
+        for i in 0..5 {
+            cache.put(i, i * 10);
+        }
+        assert_eq!(*evicted.lock().unwrap(), &[(0, 0), (1, 10)]);
+
+        cache.get(&2);
+        cache.resize(1);
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            &[(0, 0), (1, 10), (3, 30), (4, 40)]
+        );
+        assert_eq!(cache.peek_lru(), Some((&2, &20)));
+
+        cache.pop_lru();
+        cache.resize(0);
+        cache.put(5, 50);
+        assert!(cache.is_empty());
+        assert_eq!(evicted.lock().unwrap().last(), Some(&(5, 50)));
+    }
+
+    /// The obvious O(n) cache: most recently used entry first.
+    struct Model {
+        entries: Vec<(u8, u32)>,
+        capacity: usize,
+        evicted: Vec<(u8, u32)>,
+    }
+
+    impl Model {
+        fn touch(&mut self, key: u8) -> Option<u32> {
+            let pos = self.entries.iter().position(|&(k, _)| k == key)?;
+            let entry = self.entries.remove(pos);
+            self.entries.insert(0, entry);
+            Some(entry.1)
+        }
+
+        fn put(&mut self, key: u8, value: u32) -> Option<u32> {
+            if self.touch(key).is_some() {
+                return Some(std::mem::replace(&mut self.entries[0].1, value));
+            }
+            self.entries.insert(0, (key, value));
+            self.shrink();
+            None
+        }
+
+        fn shrink(&mut self) {
+            while self.entries.len() > self.capacity {
+                let entry = self.entries.pop().unwrap();
+                self.evicted.push(entry);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_model() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let log = evicted.clone();
+        let mut cache = LruCache::with_eviction_callback(8, move |key, value| {
+            log.lock().unwrap().push((key, value));
+        });
+        let mut model = Model {
+            entries: Vec::new(),
+            capacity: 8,
+            evicted: Vec::new(),
+        };
+
+        for step in 0..10_000u32 {
+            let key = next(16) as u8;
+            match next(8) {
+                0..=2 => assert_eq!(cache.put(key, step), model.put(key, step)),
+                3 | 4 => assert_eq!(cache.get(&key).copied(), model.touch(key)),
+                5 => assert_eq!(
+                    cache.peek(&key).copied(),
+                    model
+                        .entries
+                        .iter()
+                        .find(|&&(k, _)| k == key)
+                        .map(|&(_, v)| v)
+                ),
+                6 => {
+                    let expected = model
+                        .entries
+                        .iter()
+                        .position(|&(k, _)| k == key)
+                        .map(|pos| model.entries.remove(pos).1);
+                    assert_eq!(cache.remove(&key), expected);
+                }
+                _ => {
+                    if next(4) == 0 {
+                        let capacity = next(12) as usize;
+                        cache.resize(capacity);
+                        model.capacity = capacity;
+                        model.shrink();
+                    } else {
+                        assert_eq!(cache.pop_lru(), model.entries.pop());
+                    }
+                }
+            }
+            assert_eq!(cache.len(), model.entries.len());
+            assert_eq!(
+                cache.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>(),
+                model.entries
+            );
+            assert_eq!(*evicted.lock().unwrap(), model.evicted);
+        }
+    }
+}
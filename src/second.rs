@@ -1,3 +1,5 @@
+use crate::error::{try_box, AllocError};
+
 pub struct IntoIter<T>(List<T>);
 
 pub struct Iter<'a, T> {
@@ -31,6 +33,15 @@ impl<T> List<T> {
         }));
     }
 
+    /// Like [`push`](Self::push), but hands `elem` back instead of aborting if allocation fails.
+    pub fn try_push(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let mut node =
+            try_box(Node { elem, next: None }).map_err(|node| AllocError::new(node.elem))?;
+        node.next = self.head.take();
+        self.head = Some(node);
+        Ok(())
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next;
@@ -59,6 +70,12 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
@@ -1,18 +1,33 @@
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     fmt::{self, Debug},
     hash::{Hash, Hasher},
     marker::PhantomData,
+    mem,
+    ops::{Bound, Index, IndexMut, RangeBounds},
+    panic::{self, AssertUnwindSafe},
     ptr::NonNull,
+    sync::atomic::{self, AtomicU64},
+    thread,
 };
 
+#[cfg(feature = "debug-invariants")]
+use crate::error::InvariantError;
+use crate::error::{try_box, AllocError};
+
 mod cursor;
-use cursor::CursorMut;
+mod pool;
+pub use cursor::{Cursor, CursorMut};
+pub use pool::PooledLinkedList;
 
 pub struct LinkedList<T> {
     front: Link<T>,
     back: Link<T>,
     len: usize,
+    // only allocated once the list hands out a `NodeHandle`; dropped whenever nodes are moved
+    // out of the list
+    handles: Option<Box<Handles<T>>>,
 }
 
 type Link<T> = Option<NonNull<Node<T>>>;
@@ -23,6 +38,30 @@ struct Node<T> {
     elem: T,
 }
 
+/// The `NodeHandle`s a list has handed out that are still valid.
+struct Handles<T> {
+    // tells this table apart from every other list's
+    list: u64,
+    next_serial: u64,
+    live: HashMap<NonNull<Node<T>>, u64>,
+}
+
+/// A checked reference to a node of a [`LinkedList`], for O(1) lookup, removal and
+/// repositioning.
+///
+/// A handle only works with the list it was created for and only while its node is alive.
+/// Moving nodes out of a list (`split_off`, `append` from it, `drain`, ...) conservatively
+/// invalidates every handle into that list, so stale handles are rejected rather than followed.
+///
+/// Lists that never hand out a handle don't pay for them: nodes carry nothing extra and
+/// creating a list touches no shared state. The first handle a list issues allocates a table
+/// of its live handles, after which every node freed from that list costs a hash lookup.
+pub struct NodeHandle<T> {
+    node: NonNull<Node<T>>,
+    list: u64,
+    serial: u64,
+}
+
 pub struct IntoIter<T>(LinkedList<T>);
 
 pub struct Iter<'a, T> {
@@ -39,6 +78,18 @@ pub struct IterMut<'a, T> {
     _type_data: PhantomData<&'a mut T>,
 }
 
+fn next_list_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed)
+}
+
+/// Iterator returned by [`LinkedList::extract_if`].
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut LinkedList<T>,
+    next: Link<T>,
+    pred: F,
+}
+
 #[allow(dead_code)]
 fn assert_properties() {
     fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> {
@@ -79,6 +130,7 @@ impl<T> LinkedList<T> {
             front: None,
             back: None,
             len: 0,
+            handles: None,
         }
     }
 
@@ -124,8 +176,25 @@ impl<T> LinkedList<T> {
         self.len += 1;
     }
 
+    /// Like [`push_front`](Self::push_front), but hands `elem` back instead of aborting if
+    /// allocation fails.
+    pub fn try_push_front(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let node = Node::try_new(elem)?;
+        unsafe { self.attach_before(node, self.front) };
+        Ok(())
+    }
+
+    /// Like [`push_back`](Self::push_back), but hands `elem` back instead of aborting if
+    /// allocation fails.
+    pub fn try_push_back(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let node = Node::try_new(elem)?;
+        unsafe { self.attach_before(node, None) };
+        Ok(())
+    }
+
     pub fn pop_front(&mut self) -> Option<T> {
         self.front.take().map(|node| {
+            self.forget_handle(node);
             let boxed_node = unsafe { Box::from_raw(node.as_ptr()) };
             let result = boxed_node.elem;
 
@@ -146,6 +215,7 @@ impl<T> LinkedList<T> {
 
     pub fn pop_back(&mut self) -> Option<T> {
         self.back.take().map(|node| {
+            self.forget_handle(node);
             let boxed_node = unsafe { Box::from_raw(node.as_ptr()) };
             let result = boxed_node.elem;
 
@@ -164,6 +234,104 @@ impl<T> LinkedList<T> {
         })
     }
 
+    /// Moves all elements of `other` onto the back of `self` in O(1), leaving `other` empty.
+    pub fn append(&mut self, other: &mut Self) {
+        match self.back {
+            None => mem::swap(self, other),
+            Some(back) => {
+                if let Some(other_front) = other.front.take() {
+                    unsafe {
+                        (*back.as_ptr()).back = Some(other_front);
+                        (*other_front.as_ptr()).front = Some(back);
+                    }
+                    self.back = other.back.take();
+                    self.len += mem::take(&mut other.len);
+                    other.invalidate_handles();
+                }
+            }
+        }
+    }
+
+    /// Moves all elements of `other` onto the front of `self` in O(1), leaving `other` empty.
+    pub fn prepend(&mut self, other: &mut Self) {
+        match self.front {
+            None => mem::swap(self, other),
+            Some(front) => {
+                if let Some(other_back) = other.back.take() {
+                    unsafe {
+                        (*front.as_ptr()).front = Some(other_back);
+                        (*other_back.as_ptr()).back = Some(front);
+                    }
+                    self.front = other.front.take();
+                    self.len += mem::take(&mut other.len);
+                    other.invalidate_handles();
+                }
+            }
+        }
+    }
+
+    /// Splits the list in two at the given index, returning everything from `at` onwards.
+    ///
+    /// Walks from whichever end is closer to `at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len;
+        assert!(
+            at <= len,
+            "split_off index (is {at}) should be <= len (is {len})"
+        );
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == len {
+            return Self::new();
+        }
+        let front = self.cursor_at(at).split_before();
+        mem::replace(self, front)
+    }
+
+    /// Detaches the elements in `range` and returns them as an iterator.
+    ///
+    /// The range is unlinked up front, so the returned iterator owns its elements and the
+    /// list is already shortened when this returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end or the end is greater than `len`.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> IntoIter<T> {
+        let (start, end) = self.range_indices(range);
+        let mut tail = self.split_off(end);
+        let drained = self.split_off(start);
+        self.append(&mut tail);
+        drained.into_iter()
+    }
+
+    /// Replaces the elements in `range` with the items of `replace_with`, returning the
+    /// removed elements.
+    ///
+    /// `replace_with` is collected before the list is touched, so a panicking iterator
+    /// leaves the list as it was.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end or the end is greater than `len`.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> IntoIter<T>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let (start, end) = self.range_indices(range);
+        let mut middle: Self = replace_with.into_iter().collect();
+        let mut tail = self.split_off(end);
+        let removed = self.split_off(start);
+        middle.append(&mut tail);
+        self.append(&mut middle);
+        removed.into_iter()
+    }
+
     #[must_use]
     pub fn front(&self) -> Option<&T> {
         self.front.map(|node| unsafe { &(*node.as_ptr()).elem })
@@ -184,6 +352,377 @@ impl<T> LinkedList<T> {
         self.back.map(|node| unsafe { &mut (*node.as_ptr()).elem })
     }
 
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.node_at(index)
+            .map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.node_at(index)
+            .map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Inserts `elem` so that it ends up at `index`, shifting everything after it back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        let len = self.len;
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        if index == len {
+            self.push_back(elem);
+        } else {
+            self.cursor_at(index).insert_before(elem);
+        }
+    }
+
+    /// Like [`insert`](Self::insert), but hands `elem` back instead of aborting if allocation
+    /// fails.
+    pub fn try_insert(&mut self, index: usize, elem: T) -> Result<(), AllocError<T>> {
+        let len = self.len;
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        let node = Node::try_new(elem)?;
+        let next = self.node_at(index);
+        unsafe { self.attach_before(node, next) };
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, or `None` if it is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index < self.len {
+            self.cursor_at(index).remove_current()
+        } else {
+            None
+        }
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, visiting each once in order.
+    ///
+    /// If `f` panics, the elements removed so far stay removed and the list is left intact.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem));
+    }
+
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                cur = (*node.as_ptr()).back;
+                if !f(&mut (*node.as_ptr()).elem) {
+                    drop(self.unlink_node(node));
+                }
+            }
+        }
+    }
+
+    /// Returns an iterator that unlinks and yields every element for which `pred` returns `true`.
+    ///
+    /// Elements are visited front to back as the iterator is driven. Dropping the iterator early
+    /// leaves every element that hasn't been yielded in the list.
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, pred: F) -> ExtractIf<'_, T, F> {
+        ExtractIf {
+            next: self.front,
+            list: self,
+            pred,
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Sorts the list with a stable, bottom-up merge sort that only relinks nodes.
+    ///
+    /// Adjacent runs are merged in place by moving nodes of the right run in front of the
+    /// first larger node of the left run, so no extra memory is used. Every relink finishes
+    /// before `compare` is called again, which means a panicking comparator leaves a valid,
+    /// partially sorted list behind.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        let mut width = 1;
+        while width < self.len {
+            let mut run = self.front;
+            while let Some(left) = run {
+                unsafe {
+                    let mut right = Some(left);
+                    for _ in 0..width {
+                        right = right.and_then(|node| (*node.as_ptr()).back);
+                    }
+                    let Some(mut right) = right else {
+                        break;
+                    };
+
+                    let mut after = Some(right);
+                    let mut right_len = 0;
+                    while right_len < width {
+                        match after {
+                            Some(node) => after = (*node.as_ptr()).back,
+                            None => break,
+                        }
+                        right_len += 1;
+                    }
+
+                    let mut left = left;
+                    while left != right && right_len > 0 {
+                        if compare(&(*right.as_ptr()).elem, &(*left.as_ptr()).elem)
+                            == Ordering::Less
+                        {
+                            let next_right = (*right.as_ptr()).back;
+                            self.detach_node(right);
+                            self.attach_before(right, Some(left));
+                            right_len -= 1;
+                            if let Some(next_right) = next_right {
+                                right = next_right;
+                            }
+                        } else {
+                            left = (*left.as_ptr()).back.unwrap_unchecked();
+                        }
+                    }
+                    run = after;
+                }
+            }
+            width *= 2;
+        }
+    }
+
+    /// Merges the sorted list `other` into the sorted list `self`, leaving `other` empty.
+    ///
+    /// Nodes are relinked rather than reallocated, and on ties the element from `self` comes
+    /// first. If `compare` panics, both lists stay valid, with some of `other` already moved.
+    pub fn merge<F: FnMut(&T, &T) -> Ordering>(&mut self, other: &mut Self, mut compare: F) {
+        other.invalidate_handles();
+        let mut cur = self.front;
+        while let Some(theirs) = other.front {
+            let Some(ours) = cur else {
+                self.append(other);
+                return;
+            };
+            unsafe {
+                if compare(&(*theirs.as_ptr()).elem, &(*ours.as_ptr()).elem) == Ordering::Less {
+                    other.detach_node(theirs);
+                    self.attach_before(theirs, Some(ours));
+                } else {
+                    cur = (*ours.as_ptr()).back;
+                }
+            }
+        }
+    }
+
+    /// Merges any number of sorted lists into one sorted list.
+    ///
+    /// Lists are merged pairwise in rounds, so each element is compared O(log k) times for
+    /// k lists. Ties are resolved in favour of the list that came earlier.
+    pub fn merge_all<I, F>(lists: I, mut compare: F) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut lists: Vec<Self> = lists.into_iter().collect();
+        let mut step = 1;
+        while step < lists.len() {
+            for i in (0..lists.len() - step).step_by(2 * step) {
+                let (left, right) = lists.split_at_mut(i + step);
+                left[i].merge(&mut right[0], &mut compare);
+            }
+            step *= 2;
+        }
+        lists.into_iter().next().unwrap_or_default()
+    }
+
+    /// Removes consecutive repeated elements, keeping the first of each run.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes consecutive elements that `same_bucket` considers equal.
+    ///
+    /// As with `Vec::dedup_by`, `same_bucket` is passed the candidate for removal first and
+    /// the element it is being compared against (the last one kept) second.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+        self.dedup_with(same_bucket, |node| unsafe {
+            drop(Box::from_raw(node.as_ptr()));
+        });
+    }
+
+    /// Like [`dedup`](Self::dedup), but moves the removed nodes onto the back of `removed`.
+    pub fn dedup_into(&mut self, removed: &mut Self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by_into(removed, |a, b| a == b);
+    }
+
+    pub fn dedup_by_into<F: FnMut(&mut T, &mut T) -> bool>(
+        &mut self,
+        removed: &mut Self,
+        same_bucket: F,
+    ) {
+        self.invalidate_handles();
+        self.dedup_with(same_bucket, |node| unsafe {
+            removed.attach_before(node, None);
+        });
+    }
+
+    fn dedup_with<F, G>(&mut self, mut same_bucket: F, mut dispose: G)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+        G: FnMut(NonNull<Node<T>>),
+    {
+        let Some(mut kept) = self.front else {
+            return;
+        };
+        unsafe {
+            while let Some(node) = (*kept.as_ptr()).back {
+                if same_bucket(&mut (*node.as_ptr()).elem, &mut (*kept.as_ptr()).elem) {
+                    self.detach_node(node);
+                    self.forget_handle(node);
+                    dispose(node);
+                } else {
+                    kept = node;
+                }
+            }
+        }
+    }
+
+    /// Reverses the list in place by swapping every node's links.
+    ///
+    /// No elements are moved: the element that was at index `i` ends up at `len - 1 - i`.
+    /// Any cursor has to be created again afterwards, and will report indices in the new order.
+    pub fn reverse(&mut self) {
+        let mut cur = self.front;
+        while let Some(node) = cur {
+            unsafe {
+                let node = &mut *node.as_ptr();
+                mem::swap(&mut node.front, &mut node.back);
+                cur = node.front;
+            }
+        }
+        mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Rotates the list `n` places to the left, so the element at index `n % len` becomes the
+    /// front.
+    ///
+    /// The chain is closed into a ring and cut again at the new front, so only
+    /// `min(n, len - n)` nodes are walked and nothing is moved or reallocated.
+    pub fn rotate_left(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        let n = n % self.len;
+        if n == 0 {
+            return;
+        }
+        unsafe {
+            let front = self.front.unwrap_unchecked();
+            let back = self.back.unwrap_unchecked();
+            let new_front = self.node_at(n).unwrap_unchecked();
+            (*back.as_ptr()).back = Some(front);
+            (*front.as_ptr()).front = Some(back);
+            let new_back = (*new_front.as_ptr()).front.take().unwrap_unchecked();
+            (*new_back.as_ptr()).back = None;
+            self.front = Some(new_front);
+            self.back = Some(new_back);
+        }
+    }
+
+    /// Rotates the list `n` places to the right, so the element at index `len - n % len`
+    /// becomes the front.
+    pub fn rotate_right(&mut self, n: usize) {
+        if self.len == 0 {
+            return;
+        }
+        self.rotate_left(self.len - n % self.len);
+    }
+
+    /// Like [`push_front`](Self::push_front), but returns a handle to the new node.
+    pub fn push_front_handle(&mut self, elem: T) -> NodeHandle<T> {
+        let node = Node::new(elem);
+        unsafe {
+            self.attach_before(node, self.front);
+        }
+        self.issue_handle(node)
+    }
+
+    /// Like [`push_back`](Self::push_back), but returns a handle to the new node.
+    pub fn push_back_handle(&mut self, elem: T) -> NodeHandle<T> {
+        let node = Node::new(elem);
+        unsafe {
+            self.attach_before(node, None);
+        }
+        self.issue_handle(node)
+    }
+
+    /// Returns the element behind `handle`, or `None` if the handle is stale or from another list.
+    #[must_use]
+    pub fn get_handle(&self, handle: &NodeHandle<T>) -> Option<&T> {
+        self.handle_node(handle)
+            .map(|node| unsafe { &(*node.as_ptr()).elem })
+    }
+
+    #[must_use]
+    pub fn get_handle_mut(&mut self, handle: &NodeHandle<T>) -> Option<&mut T> {
+        self.handle_node(handle)
+            .map(|node| unsafe { &mut (*node.as_ptr()).elem })
+    }
+
+    /// Removes the element behind `handle` in O(1), or returns `None` if the handle is stale
+    /// or from another list.
+    pub fn remove_handle(&mut self, handle: &NodeHandle<T>) -> Option<T> {
+        self.handle_node(handle)
+            .map(|node| unsafe { self.unlink_node(node).elem })
+    }
+
+    /// Relinks the node behind `handle` at the front of the list in O(1).
+    ///
+    /// Returns `false` without touching the list if the handle is stale or from another list.
+    pub fn move_to_front(&mut self, handle: &NodeHandle<T>) -> bool {
+        let Some(node) = self.handle_node(handle) else {
+            return false;
+        };
+        unsafe {
+            self.detach_node(node);
+            self.attach_before(node, self.front);
+        }
+        true
+    }
+
+    /// Relinks the node behind `handle` at the back of the list in O(1).
+    ///
+    /// Returns `false` without touching the list if the handle is stale or from another list.
+    pub fn move_to_back(&mut self, handle: &NodeHandle<T>) -> bool {
+        let Some(node) = self.handle_node(handle) else {
+            return false;
+        };
+        unsafe {
+            self.detach_node(node);
+            self.attach_before(node, None);
+        }
+        true
+    }
+
     #[must_use]
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
@@ -214,15 +753,307 @@ impl<T> LinkedList<T> {
         self.len == 0
     }
 
+    /// Checks the list's links and length, reporting the first inconsistency found.
+    #[cfg(feature = "debug-invariants")]
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut prev = None;
+        let mut cur = self.front;
+        let mut count = 0;
+        // checking each back link before moving on also catches cycles
+        while let Some(node) = cur {
+            unsafe {
+                if (*node.as_ptr()).front != prev {
+                    return Err(InvariantError::BrokenLink { index: count });
+                }
+                cur = (*node.as_ptr()).back;
+            }
+            prev = Some(node);
+            count += 1;
+        }
+        if prev != self.back {
+            return Err(InvariantError::BackMismatch);
+        }
+        if count != self.len {
+            return Err(InvariantError::LenMismatch {
+                len: self.len,
+                actual: count,
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes and drops every element.
+    ///
+    /// If an element's destructor panics, the remaining elements are still dropped before the
+    /// panic resumes, and any further panics from them are swallowed.
     pub fn clear(&mut self) {
-        while self.pop_front().is_some() {}
+        self.invalidate_handles();
+        // already unwinding: a panic escaping from here would abort
+        if thread::panicking() {
+            self.clear_catching();
+            return;
+        }
+
+        struct DropGuard<'a, T>(&'a mut LinkedList<T>);
+
+        impl<T> Drop for DropGuard<'_, T> {
+            fn drop(&mut self) {
+                self.0.clear_catching();
+            }
+        }
+
+        let guard = DropGuard(self);
+        while guard.0.pop_front().is_some() {}
+        mem::forget(guard);
+    }
+
+    /// Drops every element, carrying on past panicking destructors.
+    fn clear_catching(&mut self) {
+        while let Some(elem) = self.pop_front() {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(elem)));
+        }
+    }
+
+    pub fn cursor(&self) -> Cursor<'_, T> {
+        Cursor::on_list(self)
     }
 
-    pub fn cursor_mut(&mut self) -> CursorMut<T> {
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
         CursorMut::on_list(self)
     }
+
+    /// Returns a cursor on the front element, or on the ghost if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        let mut cursor = self.cursor();
+        cursor.move_next();
+        cursor
+    }
+
+    /// Returns a cursor on the back element, or on the ghost if the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        let mut cursor = self.cursor();
+        cursor.move_prev();
+        cursor
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        cursor
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.move_prev();
+        cursor
+    }
+
+    /// Builds a list out of a detached chain of `len` nodes running from `front` to `back`.
+    fn from_chain(front: NonNull<Node<T>>, back: NonNull<Node<T>>, len: usize) -> Self {
+        Self {
+            front: Some(front),
+            back: Some(back),
+            len,
+            handles: None,
+        }
+    }
+
+    /// Forgets every `NodeHandle` into the list, so that they are all rejected.
+    ///
+    /// Must be called whenever nodes are moved out of the list into another one.
+    fn invalidate_handles(&mut self) {
+        self.handles = None;
+    }
+
+    /// Records a handle for `node`, which must be a node of this list.
+    fn issue_handle(&mut self, node: NonNull<Node<T>>) -> NodeHandle<T> {
+        let handles = self.handles.get_or_insert_with(|| {
+            Box::new(Handles {
+                list: next_list_id(),
+                next_serial: 0,
+                live: HashMap::new(),
+            })
+        });
+        let serial = handles.next_serial;
+        handles.next_serial += 1;
+        handles.live.insert(node, serial);
+        NodeHandle {
+            node,
+            list: handles.list,
+            serial,
+        }
+    }
+
+    /// Drops the handle for `node`, if it has one. Must be called whenever a node is freed.
+    fn forget_handle(&mut self, node: NonNull<Node<T>>) {
+        if let Some(handles) = &mut self.handles {
+            handles.live.remove(&node);
+        }
+    }
+
+    /// Returns the handle's node if it is alive and belongs to this list.
+    fn handle_node(&self, handle: &NodeHandle<T>) -> Link<T> {
+        let handles = self.handles.as_ref()?;
+        (handles.list == handle.list && handles.live.get(&handle.node) == Some(&handle.serial))
+            .then_some(handle.node)
+    }
+
+    fn range_indices<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start
+                .checked_add(1)
+                .expect("attempted to index slice from after maximum usize"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end
+                .checked_add(1)
+                .expect("attempted to index slice up to maximum usize"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            start <= end,
+            "range start (is {start}) should be <= range end (is {end})"
+        );
+        assert!(
+            end <= self.len,
+            "range end (is {end}) should be <= len (is {})",
+            self.len
+        );
+        (start, end)
+    }
+
+    /// Detaches `node` from the list and hands back ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) -> Box<Node<T>> {
+        self.detach_node(node);
+        self.forget_handle(node);
+        Box::from_raw(node.as_ptr())
+    }
+
+    /// Detaches `node` from its neighbours, leaving it allocated with both links cleared.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    unsafe fn detach_node(&mut self, node: NonNull<Node<T>>) {
+        let node = node.as_ptr();
+        match (*node).front {
+            Some(front) => (*front.as_ptr()).back = (*node).back,
+            None => self.front = (*node).back,
+        }
+        match (*node).back {
+            Some(back) => (*back.as_ptr()).front = (*node).front,
+            None => self.back = (*node).front,
+        }
+        (*node).front = None;
+        (*node).back = None;
+        self.len -= 1;
+    }
+
+    /// Links a detached `node` in before `next`, or at the back if `next` is `None`.
+    ///
+    /// # Safety
+    ///
+    /// `node` must not be part of any list and `next` must be a node of this list.
+    unsafe fn attach_before(&mut self, node: NonNull<Node<T>>, next: Link<T>) {
+        let prev = match next {
+            Some(next) => (*next.as_ptr()).front.replace(node),
+            None => self.back.replace(node),
+        };
+        match prev {
+            Some(prev) => (*prev.as_ptr()).back = Some(node),
+            None => self.front = Some(node),
+        }
+        (*node.as_ptr()).front = prev;
+        (*node.as_ptr()).back = next;
+        self.len += 1;
+    }
+
+    /// Returns the node at `index`, walking from the nearer end.
+    fn node_at(&self, index: usize) -> Link<T> {
+        if index >= self.len {
+            return None;
+        }
+        unsafe {
+            if index < self.len - index {
+                let mut node = self.front?;
+                for _ in 0..index {
+                    node = (*node.as_ptr()).back?;
+                }
+                Some(node)
+            } else {
+                let mut node = self.back?;
+                for _ in index + 1..self.len {
+                    node = (*node.as_ptr()).front?;
+                }
+                Some(node)
+            }
+        }
+    }
+
+    /// Returns a cursor on the element at `index`, walking from the nearer end.
+    fn cursor_at(&mut self, index: usize) -> CursorMut<'_, T> {
+        debug_assert!(index < self.len);
+        let mut cursor = self.cursor_mut();
+        cursor.seek_to(index);
+        cursor
+    }
 }
 
+impl<T> Node<T> {
+    /// Allocates a detached node, handing `elem` back if the allocation fails.
+    fn try_new(elem: T) -> Result<NonNull<Self>, AllocError<T>> {
+        let node = try_box(Node {
+            front: None,
+            back: None,
+            elem,
+        })
+        .map_err(|node| AllocError::new(node.elem))?;
+        Ok(unsafe { NonNull::new_unchecked(Box::into_raw(node)) })
+    }
+
+    /// Allocates a detached node.
+    fn new(elem: T) -> NonNull<Self> {
+        unsafe {
+            NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+                front: None,
+                back: None,
+                elem,
+            })))
+        }
+    }
+}
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            list: self.list,
+            serial: self.serial,
+        }
+    }
+}
+
+impl<T> Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.node)
+            .field("list", &self.list)
+            .field("serial", &self.serial)
+            .finish()
+    }
+}
+
+// a handle is only ever dereferenced through the list that owns its node
+unsafe impl<T> Send for NodeHandle<T> {}
+unsafe impl<T> Sync for NodeHandle<T> {}
+
 impl<T> Default for LinkedList<T> {
     fn default() -> Self {
         Self::new()
@@ -231,6 +1062,8 @@ impl<T> Default for LinkedList<T> {
 
 impl<T: Clone> Clone for LinkedList<T> {
     fn clone(&self) -> Self {
+        // `new_list` is a complete list after every push, so if `clone` panics it drops
+        // whatever was cloned so far
         let mut new_list = Self::new();
         for item in self {
             new_list.push_back(item.clone());
@@ -239,6 +1072,20 @@ impl<T: Clone> Clone for LinkedList<T> {
     }
 }
 
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+
+impl<T> IndexMut<usize> for LinkedList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+
 impl<T> Extend<T> for LinkedList<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for item in iter {
@@ -431,9 +1278,33 @@ impl<T> ExactSizeIterator for IterMut<'_, T> {
     }
 }
 
+impl<T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.next {
+            unsafe {
+                self.next = (*node.as_ptr()).back;
+                if (self.pred)(&mut (*node.as_ptr()).elem) {
+                    return Some(self.list.unlink_node(node).elem);
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.len))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::cmp::Ordering;
+    use std::{
+        cell::Cell,
+        cmp::Ordering,
+        panic::{self, AssertUnwindSafe},
+        rc::Rc,
+    };
 
     use super::LinkedList;
 
@@ -613,6 +1484,627 @@ mod tests {
         assert!(it.next().is_none());
     }
 
+    #[test]
+    fn test_append() {
+        let mut n = list_from(&[1, 2, 3]);
+        let mut m = list_from(&[4, 5, 6]);
+        n.append(&mut m);
+        assert_eq!(n.len(), 6);
+        assert!(m.is_empty());
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[6, 5, 4, 3, 2, 1]
+        );
+
+        let mut empty = LinkedList::new();
+        n.append(&mut empty);
+        assert_eq!(n.len(), 6);
+        empty.append(&mut n);
+        assert_eq!(empty.len(), 6);
+        assert!(n.is_empty());
+        assert_eq!(empty.back(), Some(&6));
+
+        m.push_back(7);
+        empty.append(&mut m);
+        assert_eq!(empty.pop_back(), Some(7));
+        assert_eq!(empty.pop_back(), Some(6));
+        assert_eq!(empty.len(), 5);
+    }
+
+    #[test]
+    fn test_prepend() {
+        let mut n = list_from(&[4, 5, 6]);
+        let mut m = list_from(&[1, 2, 3]);
+        n.prepend(&mut m);
+        assert_eq!(n.len(), 6);
+        assert!(m.is_empty());
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[6, 5, 4, 3, 2, 1]
+        );
+
+        let mut empty = LinkedList::new();
+        n.prepend(&mut empty);
+        assert_eq!(n.len(), 6);
+        empty.prepend(&mut n);
+        assert_eq!(empty.len(), 6);
+        assert!(n.is_empty());
+
+        m.push_back(0);
+        empty.prepend(&mut m);
+        assert_eq!(empty.pop_front(), Some(0));
+        assert_eq!(empty.pop_front(), Some(1));
+        assert_eq!(empty.len(), 5);
+    }
+
+    #[test]
+    fn test_split_off() {
+        for at in 0..=7 {
+            let mut n = generate_test();
+            let m = n.split_off(at);
+            assert_eq!(n.len(), at);
+            assert_eq!(m.len(), 7 - at);
+            assert_eq!(
+                n.iter().copied().collect::<Vec<_>>(),
+                (0..at as i32).collect::<Vec<_>>()
+            );
+            assert_eq!(n.iter().rev().count(), at);
+            assert_eq!(
+                m.iter().copied().collect::<Vec<_>>(),
+                (at as i32..7).collect::<Vec<_>>()
+            );
+            assert_eq!(m.iter().rev().count(), 7 - at);
+        }
+
+        let mut n = list_from(&[1, 2]);
+        let mut m = n.split_off(1);
+        assert_eq!(n.pop_front(), Some(1));
+        assert_eq!(n.pop_front(), None);
+        assert_eq!(m.pop_back(), Some(2));
+        assert_eq!(m.pop_back(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut n = generate_test();
+        n.split_off(8);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut n = generate_test();
+        for i in 0..7 {
+            assert_eq!(n.get(i), Some(&(i as i32)));
+            assert_eq!(n[i], i as i32);
+        }
+        assert_eq!(n.get(7), None);
+        *n.get_mut(2).unwrap() = 20;
+        n[5] = 50;
+        assert_eq!(n.get_mut(7), None);
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[0, 1, 20, 3, 4, 50, 6]
+        );
+        assert_eq!(LinkedList::<i32>::new().get(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let n = generate_test();
+        let _ = n[7];
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut n = LinkedList::new();
+        n.insert(0, 3);
+        n.insert(0, 1);
+        n.insert(1, 2);
+        n.insert(3, 5);
+        n.insert(3, 4);
+        assert_eq!(n.len(), 5);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4, 5]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[5, 4, 3, 2, 1]
+        );
+
+        assert_eq!(n.remove(5), None);
+        assert_eq!(n.remove(3), Some(4));
+        assert_eq!(n.remove(0), Some(1));
+        assert_eq!(n.remove(2), Some(5));
+        assert_eq!(n.len(), 2);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[2, 3]);
+        assert_eq!(n.back(), Some(&3));
+        assert_eq!(n.remove(1), Some(3));
+        assert_eq!(n.remove(0), Some(2));
+        assert!(n.is_empty());
+        assert_eq!(n.front(), None);
+        assert_eq!(n.back(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_bounds() {
+        let mut n = generate_test();
+        n.insert(8, 0);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut n: LinkedList<i32> = (0..10).collect();
+        n.retain(|x| x % 3 == 0);
+        assert_eq!(n.len(), 4);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[0, 3, 6, 9]);
+        assert_eq!(n.iter().rev().copied().collect::<Vec<_>>(), &[9, 6, 3, 0]);
+
+        n.retain_mut(|x| {
+            *x += 1;
+            *x != 1 && *x != 10
+        });
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[4, 7]);
+        assert_eq!(n.front(), Some(&4));
+        assert_eq!(n.back(), Some(&7));
+
+        n.retain(|_| false);
+        assert!(n.is_empty());
+        assert_eq!(n.front(), None);
+        assert_eq!(n.back(), None);
+    }
+
+    #[test]
+    fn test_retain_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut n: LinkedList<i32> = (0..10).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            n.retain(|&x| {
+                assert!(x != 5);
+                x % 2 == 1
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(n.len(), 7);
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[1, 3, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[9, 8, 7, 6, 5, 3, 1]
+        );
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut n: LinkedList<i32> = (0..10).collect();
+        let evens: Vec<_> = n.extract_if(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, &[0, 2, 4, 6, 8]);
+        assert_eq!(n.len(), 5);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 3, 5, 7, 9]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[9, 7, 5, 3, 1]
+        );
+
+        {
+            let mut it = n.extract_if(|x| *x > 2);
+            assert_eq!(it.next(), Some(3));
+        }
+        assert_eq!(n.len(), 4);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 5, 7, 9]);
+
+        let mut seen = 0;
+        assert_eq!(
+            n.extract_if(|_| {
+                seen += 1;
+                true
+            })
+            .take(4)
+            .count(),
+            4
+        );
+        assert_eq!(seen, 4);
+        assert!(n.is_empty());
+        assert_eq!(n.front(), None);
+        assert_eq!(n.back(), None);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut n = generate_test();
+        let mut drained = n.drain(2..5);
+        assert_eq!(drained.len(), 3);
+        assert_eq!(drained.next_back(), Some(4));
+        assert_eq!(drained.collect::<Vec<_>>(), &[2, 3]);
+        assert_eq!(n.len(), 4);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[0, 1, 5, 6]);
+        assert_eq!(n.iter().rev().copied().collect::<Vec<_>>(), &[6, 5, 1, 0]);
+
+        assert_eq!(n.drain(..1).collect::<Vec<_>>(), &[0]);
+        assert_eq!(n.drain(2..).collect::<Vec<_>>(), &[6]);
+        assert_eq!(n.drain(1..1).count(), 0);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 5]);
+        assert_eq!(n.drain(..=1).collect::<Vec<_>>(), &[1, 5]);
+        assert!(n.is_empty());
+        assert_eq!(n.drain(..).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_out_of_bounds() {
+        let mut n = generate_test();
+        n.drain(3..8);
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut n = generate_test();
+        let removed = n.splice(1..3, [10, 11, 12]);
+        assert_eq!(removed.collect::<Vec<_>>(), &[1, 2]);
+        assert_eq!(n.len(), 8);
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[0, 10, 11, 12, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[6, 5, 4, 3, 12, 11, 10, 0]
+        );
+
+        assert_eq!(n.splice(8.., [7]).count(), 0);
+        assert_eq!(n.splice(..1, []).collect::<Vec<_>>(), &[0]);
+        assert_eq!(n.splice(.., [1, 2]).count(), 8);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2]);
+        assert_eq!(n.back(), Some(&2));
+    }
+
+    fn pseudo_random(len: usize, seed: u64) -> Vec<i32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as i32 % 50
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        for len in 0..40 {
+            let v = pseudo_random(len, len as u64);
+            let mut n = list_from(&v);
+            n.sort();
+            let mut expected = v.clone();
+            expected.sort();
+            assert_eq!(n.len(), len);
+            assert_eq!(n.iter().copied().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(n.iter().rev().copied().collect::<Vec<_>>(), expected);
+        }
+
+        let mut n = generate_test();
+        n.sort_by(|a, b| b.cmp(a));
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[6, 5, 4, 3, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_sort_stable() {
+        let v: Vec<_> = pseudo_random(100, 7)
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x % 5, i))
+            .collect();
+        let mut n = list_from(&v);
+        n.sort_by_key(|&(key, _)| key);
+        let mut expected = v.clone();
+        expected.sort_by_key(|&(key, _)| key);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_sort_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let v = pseudo_random(50, 3);
+        let mut n = list_from(&v);
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            n.sort_by(|a, b| {
+                calls += 1;
+                assert!(calls < 100);
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(n.len(), 50);
+        let mut forwards: Vec<_> = n.iter().copied().collect();
+        let mut backwards: Vec<_> = n.iter().rev().copied().collect();
+        backwards.reverse();
+        assert_eq!(forwards, backwards);
+        forwards.sort();
+        let mut expected = v.clone();
+        expected.sort();
+        assert_eq!(forwards, expected);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut n = list_from(&[(1, 'a'), (3, 'a'), (3, 'b'), (8, 'a')]);
+        let mut m = list_from(&[(0, 'x'), (3, 'x'), (4, 'x'), (9, 'x'), (10, 'x')]);
+        n.merge(&mut m, |a, b| a.0.cmp(&b.0));
+        assert!(m.is_empty());
+        assert_eq!(m.front(), None);
+        assert_eq!(n.len(), 9);
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[
+                (0, 'x'),
+                (1, 'a'),
+                (3, 'a'),
+                (3, 'b'),
+                (3, 'x'),
+                (4, 'x'),
+                (8, 'a'),
+                (9, 'x'),
+                (10, 'x')
+            ]
+        );
+        assert_eq!(n.iter().rev().count(), 9);
+        assert_eq!(n.back(), Some(&(10, 'x')));
+
+        let mut empty = LinkedList::new();
+        empty.merge(&mut n, |a, b| a.cmp(b));
+        assert_eq!(empty.len(), 9);
+        empty.merge(&mut n, |a, b| a.cmp(b));
+        assert_eq!(empty.len(), 9);
+    }
+
+    #[test]
+    fn test_merge_all() {
+        let lists = [
+            list_from(&[(2, 0), (5, 0)]),
+            list_from(&[]),
+            list_from(&[(1, 2), (2, 2), (7, 2)]),
+            list_from(&[(0, 3), (5, 3)]),
+            list_from(&[(2, 4)]),
+        ];
+        let merged = LinkedList::merge_all(lists, |a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            merged.iter().copied().collect::<Vec<_>>(),
+            &[
+                (0, 3),
+                (1, 2),
+                (2, 0),
+                (2, 2),
+                (2, 4),
+                (5, 0),
+                (5, 3),
+                (7, 2)
+            ]
+        );
+        assert_eq!(merged.iter().rev().count(), 8);
+
+        let merged = LinkedList::<i32>::merge_all([], i32::cmp);
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut n = list_from(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        n.dedup();
+        assert_eq!(n.len(), 5);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 1, 4]);
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[4, 1, 3, 2, 1]
+        );
+
+        let mut n = list_from(&[10, 11, 20, 25, 31, 42, 49]);
+        n.dedup_by_key(|x| *x / 10);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[10, 20, 31, 42]);
+        assert_eq!(n.back(), Some(&42));
+
+        let mut n = list_from(&["a", "A", "b", "B", "b"]);
+        n.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &["a", "b"]);
+
+        let mut n: LinkedList<i32> = LinkedList::new();
+        n.dedup();
+        assert!(n.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_into() {
+        let mut n = list_from(&[1, 1, 2, 3, 3, 3, 4]);
+        let mut removed = list_from(&[0]);
+        n.dedup_into(&mut removed);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[1, 2, 3, 4]);
+        assert_eq!(n.iter().rev().copied().collect::<Vec<_>>(), &[4, 3, 2, 1]);
+        assert_eq!(removed.len(), 4);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), &[0, 1, 3, 3]);
+        assert_eq!(
+            removed.iter().rev().copied().collect::<Vec<_>>(),
+            &[3, 3, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut n = generate_test();
+        n.reverse();
+        assert_eq!(n.len(), 7);
+        assert_eq!(
+            n.iter().copied().collect::<Vec<_>>(),
+            &[6, 5, 4, 3, 2, 1, 0]
+        );
+        assert_eq!(
+            n.iter().rev().copied().collect::<Vec<_>>(),
+            &[0, 1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(n[1], 5);
+
+        let mut cursor = n.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(1));
+
+        n.push_back(-1);
+        n.push_front(7);
+        assert_eq!(n.pop_back(), Some(-1));
+        assert_eq!(n.pop_back(), Some(0));
+        assert_eq!(n.pop_front(), Some(7));
+
+        let mut n = list_from(&[1]);
+        n.reverse();
+        assert_eq!(n.front(), Some(&1));
+        assert_eq!(n.back(), Some(&1));
+        let mut n: LinkedList<i32> = LinkedList::new();
+        n.reverse();
+        assert!(n.is_empty());
+    }
+
+    #[test]
+    fn test_rotate() {
+        for n in 0..16 {
+            let mut list = generate_test();
+            list.rotate_left(n);
+            let mut expected: Vec<_> = (0..7).collect();
+            expected.rotate_left(n % 7);
+            assert_eq!(list.len(), 7);
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), expected);
+
+            let mut list = generate_test();
+            list.rotate_right(n);
+            let mut expected: Vec<_> = (0..7).collect();
+            expected.rotate_right(n % 7);
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), expected);
+        }
+
+        let mut list = list_from(&[1]);
+        list.rotate_left(3);
+        list.rotate_right(5);
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        list.rotate_left(1);
+        list.rotate_right(1);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut n = LinkedList::new();
+        let two = n.push_back_handle(2);
+        let one = n.push_front_handle(1);
+        n.push_back(3);
+        let four = n.push_back_handle(4);
+        assert_eq!(n.get_handle(&one), Some(&1));
+        assert_eq!(n.get_handle(&two), Some(&2));
+        *n.get_handle_mut(&four).unwrap() = 40;
+
+        assert!(n.move_to_front(&four));
+        assert!(n.move_to_back(&one));
+        assert!(n.move_to_back(&one));
+        assert_eq!(n.len(), 4);
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[40, 2, 3, 1]);
+        assert_eq!(n.iter().rev().copied().collect::<Vec<_>>(), &[1, 3, 2, 40]);
+
+        assert_eq!(n.remove_handle(&two), Some(2));
+        assert_eq!(n.remove_handle(&two), None);
+        assert_eq!(n.get_handle(&two), None);
+        assert!(!n.move_to_front(&two));
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[40, 3, 1]);
+
+        assert_eq!(n.pop_back(), Some(1));
+        assert_eq!(n.get_handle(&one), None);
+        assert_eq!(n.remove_handle(&one.clone()), None);
+        assert_eq!(n.remove_handle(&four), Some(40));
+        assert_eq!(n.iter().copied().collect::<Vec<_>>(), &[3]);
+        assert_eq!(n.back(), Some(&3));
+    }
+
+    #[test]
+    fn test_handles_across_lists() {
+        let mut n = LinkedList::new();
+        let mut m = LinkedList::new();
+        let a = n.push_back_handle(1);
+        let b = m.push_back_handle(2);
+        assert_eq!(m.get_handle(&a), None);
+        assert_eq!(n.remove_handle(&b), None);
+        assert!(!n.move_to_front(&b));
+
+        // moving the list itself keeps its handles
+        let mut moved = std::mem::take(&mut n);
+        assert_eq!(moved.get_handle(&a), Some(&1));
+        assert_eq!(n.get_handle(&a), None);
+
+        // nodes moved into another list are no longer reachable through old handles
+        moved.append(&mut m);
+        assert_eq!(moved.get_handle(&b), None);
+        assert_eq!(m.get_handle(&b), None);
+        assert_eq!(moved.get_handle(&a), Some(&1));
+
+        let tail = moved.split_off(1);
+        assert_eq!(moved.get_handle(&a), None);
+        assert_eq!(tail.get_handle(&a), None);
+        assert_eq!(tail.get_handle(&b), None);
+
+        let mut empty = LinkedList::new();
+        let c = moved.push_back_handle(3);
+        empty.append(&mut moved);
+        assert_eq!(empty.get_handle(&c), Some(&3));
+        assert_eq!(moved.get_handle(&c), None);
+
+        let cloned = empty.clone();
+        assert_eq!(cloned.get_handle(&c), None);
+    }
+
+    #[test]
+    fn test_handles_after_reuse() {
+        // freed nodes are forgotten, so a handle never resolves to whatever is allocated
+        // in their place
+        let mut n = LinkedList::new();
+        let a = n.push_back_handle(1);
+        assert_eq!(n.pop_back(), Some(1));
+        for i in 0..8 {
+            n.push_back(i);
+        }
+        let b = n.push_back_handle(8);
+        assert_eq!(n.get_handle(&a), None);
+        assert_eq!(n.get_handle(&b), Some(&8));
+
+        n.push_back(8);
+        let c = n.push_front_handle(0);
+        n.dedup();
+        assert_eq!(n.len(), 9);
+        assert_eq!(n.get_handle(&c), Some(&0));
+        assert_eq!(n.get_handle(&b), Some(&8));
+        n.clear();
+        assert_eq!(n.get_handle(&b), None);
+    }
+
+    #[test]
+    fn test_plain_nodes_stay_small() {
+        assert_eq!(
+            std::mem::size_of::<super::Node<u64>>(),
+            3 * std::mem::size_of::<usize>()
+        );
+    }
+
     #[test]
     fn test_eq() {
         let mut n: LinkedList<u8> = list_from(&[]);
@@ -688,4 +2180,124 @@ mod tests {
 
         assert!(map.is_empty());
     }
+
+    /// Counts its drops in `drops`, and panics when dropped or cloned if told to.
+    struct Bomb {
+        drops: Rc<Cell<usize>>,
+        panic_on_drop: bool,
+        panic_on_clone: bool,
+    }
+
+    impl Bomb {
+        fn new(drops: &Rc<Cell<usize>>, panic_on_drop: bool, panic_on_clone: bool) -> Self {
+            Self {
+                drops: drops.clone(),
+                panic_on_drop,
+                panic_on_clone,
+            }
+        }
+    }
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            assert!(!self.panic_on_clone, "clone bomb");
+            Self::new(&self.drops, self.panic_on_drop, false)
+        }
+    }
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            assert!(!self.panic_on_drop, "drop bomb");
+        }
+    }
+
+    #[test]
+    fn test_clear_panic_safety() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list: LinkedList<_> = (0..6).map(|i| Bomb::new(&drops, i == 2, false)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| list.clear()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 6);
+        assert!(list.is_empty());
+        assert!(list.front().is_none() && list.back().is_none());
+
+        list.push_back(Bomb::new(&drops, false, false));
+        drop(list);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn test_drop_panic_safety() {
+        // several panicking destructors must not abort, and must not stop the rest being freed
+        let drops = Rc::new(Cell::new(0));
+        let list: LinkedList<_> = (0..8)
+            .map(|i| Bomb::new(&drops, i % 3 == 0, false))
+            .collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(list)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 8);
+    }
+
+    #[test]
+    fn test_clone_panic_safety() {
+        let drops = Rc::new(Cell::new(0));
+        let list: LinkedList<_> = (0..5).map(|i| Bomb::new(&drops, false, i == 3)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| list.clone()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+        drop(list);
+        assert_eq!(drops.get(), 8);
+
+        // the partial clone's elements panic while it is dropped during unwinding
+        let drops = Rc::new(Cell::new(0));
+        let list: LinkedList<_> = (0..5).map(|i| Bomb::new(&drops, i < 2, i == 3)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| list.clone()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(list)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 8);
+    }
+
+    #[cfg(feature = "debug-invariants")]
+    #[test]
+    fn test_validate() {
+        use crate::error::InvariantError;
+
+        let mut list = generate_test();
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(LinkedList::<i32>::new().validate(), Ok(()));
+
+        list.len += 1;
+        assert_eq!(
+            list.validate(),
+            Err(InvariantError::LenMismatch { len: 8, actual: 7 })
+        );
+        list.len -= 1;
+
+        let back = list.back.take();
+        assert_eq!(list.validate(), Err(InvariantError::BackMismatch));
+        list.back = list.front;
+        assert_eq!(list.validate(), Err(InvariantError::BackMismatch));
+        list.back = back;
+
+        let (third, fourth) = (list.node_at(2).unwrap(), list.node_at(3).unwrap());
+        unsafe {
+            (*fourth.as_ptr()).front = None;
+            assert_eq!(
+                list.validate(),
+                Err(InvariantError::BrokenLink { index: 3 })
+            );
+            // a cycle back to the front is caught rather than followed forever
+            let saved = (*third.as_ptr()).back.replace(list.front.unwrap());
+            assert_eq!(
+                list.validate(),
+                Err(InvariantError::BrokenLink { index: 3 })
+            );
+            (*third.as_ptr()).back = saved;
+            (*fourth.as_ptr()).front = Some(third);
+        }
+        assert_eq!(list.validate(), Ok(()));
+    }
 }
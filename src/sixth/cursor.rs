@@ -1,6 +1,15 @@
 use std::mem;
 
-use super::{Link, LinkedList};
+use super::{Link, LinkedList, Node, NodeHandle};
+use crate::error::AllocError;
+#[cfg(feature = "debug-invariants")]
+use crate::error::InvariantError;
+
+pub struct Cursor<'a, T> {
+    cur: Link<T>,
+    list: &'a LinkedList<T>,
+    index: usize,
+}
 
 pub struct CursorMut<'a, T> {
     cur: Link<T>,
@@ -8,6 +17,98 @@ pub struct CursorMut<'a, T> {
     index: usize,
 }
 
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cursor<'_, T> {}
+
+unsafe impl<T: Sync> Send for Cursor<'_, T> {}
+unsafe impl<T: Sync> Sync for Cursor<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn on_list(list: &'a LinkedList<T>) -> Self {
+        Cursor {
+            list,
+            cur: None,
+            index: 0,
+        }
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.cur?;
+        Some(self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur.take() {
+            self.cur = unsafe { (*cur.as_ptr()).back };
+            self.index += 1;
+        } else {
+            self.cur = self.list.front;
+            self.index = 0;
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur.take() {
+            self.cur = unsafe { (*cur.as_ptr()).front };
+            self.index = self.index.wrapping_sub(1);
+        } else {
+            self.cur = self.list.back;
+            self.index = self.list.len.wrapping_sub(1);
+        }
+    }
+
+    /// Moves forward to the next element matching `pred`, or to the ghost if none does.
+    pub fn find_next<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&'a T> {
+        loop {
+            self.move_next();
+            match self.current() {
+                Some(elem) if !pred(elem) => continue,
+                found => return found,
+            }
+        }
+    }
+
+    /// Moves backward to the previous element matching `pred`, or to the ghost if none does.
+    pub fn find_prev<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&'a T> {
+        loop {
+            self.move_prev();
+            match self.current() {
+                Some(elem) if !pred(elem) => continue,
+                found => return found,
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.cur.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            match self.cur {
+                Some(cur) => (*cur.as_ptr()).back,
+                None => self.list.front,
+            }
+            .map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            match self.cur {
+                Some(cur) => (*cur.as_ptr()).front,
+                None => self.list.back,
+            }
+            .map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+}
+
 impl<'a, T> CursorMut<'a, T> {
     pub fn on_list(list: &'a mut LinkedList<T>) -> Self {
         CursorMut {
@@ -22,6 +123,37 @@ impl<'a, T> CursorMut<'a, T> {
         Some(self.index)
     }
 
+    /// Returns a read-only cursor at the same position, borrowing this one.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+
+    /// Validates the list, then checks that the cursor's node is in it at `index`.
+    #[cfg(feature = "debug-invariants")]
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.list.validate()?;
+        let Some(cur) = self.cur else {
+            return Ok(());
+        };
+        let mut node = self.list.front;
+        let mut actual = 0;
+        while node != Some(cur) {
+            node = unsafe { (*node.ok_or(InvariantError::CursorNotInList)?.as_ptr()).back };
+            actual += 1;
+        }
+        if actual != self.index {
+            return Err(InvariantError::CursorIndexMismatch {
+                index: self.index,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
     pub fn move_next(&mut self) {
         if let Some(cur) = self.cur.take() {
             self.cur = unsafe { (*cur.as_ptr()).back };
@@ -44,6 +176,102 @@ impl<'a, T> CursorMut<'a, T> {
         }
     }
 
+    /// Moves the cursor to the element at `index`, or to the ghost if `index == len`.
+    ///
+    /// The cursor takes whichever route is shortest: from where it is, from the front or
+    /// from the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn seek_to(&mut self, index: usize) {
+        let len = self.list.len;
+        assert!(
+            index <= len,
+            "seek index (is {index}) should be <= len (is {len})"
+        );
+        // positions form a ring of len + 1 slots, with the ghost at position len
+        let ring = len + 1;
+        let pos = self.position();
+        let forward = (index + ring - pos) % ring;
+        let backward = (pos + ring - index) % ring;
+        let from_ghost_forward = (index + 1) % ring;
+        let from_ghost_backward = len - index;
+
+        let (from_ghost, steps, ahead) = [
+            (false, forward, true),
+            (false, backward, false),
+            (true, from_ghost_forward, true),
+            (true, from_ghost_backward, false),
+        ]
+        .into_iter()
+        .min_by_key(|&(_, steps, _)| steps)
+        .unwrap_or((false, 0, true));
+
+        if from_ghost {
+            self.cur = None;
+        }
+        for _ in 0..steps {
+            if ahead {
+                self.move_next();
+            } else {
+                self.move_prev();
+            }
+        }
+    }
+
+    /// Moves the cursor `offset` places, wrapping through the ghost like repeated calls to
+    /// [`move_next`](Self::move_next) or [`move_prev`](Self::move_prev) would.
+    ///
+    /// Returns `true` if the cursor ends up on the ghost.
+    pub fn move_by(&mut self, offset: isize) -> bool {
+        let ring = self.list.len + 1;
+        let pos = self.position();
+        let steps = offset.unsigned_abs() % ring;
+        let target = if offset >= 0 {
+            (pos + steps) % ring
+        } else {
+            (pos + ring - steps) % ring
+        };
+        self.seek_to(target);
+        self.cur.is_none()
+    }
+
+    /// Returns the cursor's position on the ring of elements, where the ghost is `len`.
+    fn position(&self) -> usize {
+        if self.cur.is_some() {
+            self.index
+        } else {
+            self.list.len
+        }
+    }
+
+    /// Moves forward to the next element matching `pred`, or to the ghost if none does.
+    ///
+    /// The search starts after the current element; from the ghost it starts at the front.
+    pub fn find_next<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&mut T> {
+        loop {
+            self.move_next();
+            match self.cur {
+                Some(node) if !pred(unsafe { &(*node.as_ptr()).elem }) => continue,
+                _ => return self.current(),
+            }
+        }
+    }
+
+    /// Moves backward to the previous element matching `pred`, or to the ghost if none does.
+    ///
+    /// The search starts before the current element; from the ghost it starts at the back.
+    pub fn find_prev<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<&mut T> {
+        loop {
+            self.move_prev();
+            match self.cur {
+                Some(node) if !pred(unsafe { &(*node.as_ptr()).elem }) => continue,
+                _ => return self.current(),
+            }
+        }
+    }
+
     pub fn current(&mut self) -> Option<&mut T> {
         unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
     }
@@ -70,30 +298,38 @@ impl<'a, T> CursorMut<'a, T> {
 
     pub fn split_before(&mut self) -> LinkedList<T> {
         if let Some(cur) = self.cur {
-            let new_list = LinkedList {
-                front: self.list.front.replace(cur),
-                back: unsafe { (*cur.as_ptr()).front.take() },
-                len: self.index,
+            let Some(prev) = (unsafe { (*cur.as_ptr()).front.take() }) else {
+                return LinkedList::new();
             };
+            unsafe {
+                (*prev.as_ptr()).back = None;
+            }
+            let front = unsafe { self.list.front.replace(cur).unwrap_unchecked() };
+            let new_list = LinkedList::from_chain(front, prev, self.index);
             self.list.len -= self.index;
+            self.list.invalidate_handles();
             self.index = 0;
             new_list
         } else {
-            mem::replace(self.list, LinkedList::new())
+            mem::take(self.list)
         }
     }
 
     pub fn split_after(&mut self) -> LinkedList<T> {
         if let Some(cur) = self.cur {
-            let new_list = LinkedList {
-                front: unsafe { (*cur.as_ptr()).back.take() },
-                back: self.list.back.replace(cur),
-                len: self.list.len - self.index,
+            let Some(next) = (unsafe { (*cur.as_ptr()).back.take() }) else {
+                return LinkedList::new();
             };
+            unsafe {
+                (*next.as_ptr()).front = None;
+            }
+            let back = unsafe { self.list.back.replace(cur).unwrap_unchecked() };
+            let new_list = LinkedList::from_chain(next, back, self.list.len - self.index - 1);
             self.list.len = self.index + 1;
+            self.list.invalidate_handles();
             new_list
         } else {
-            mem::replace(self.list, LinkedList::new())
+            mem::take(self.list)
         }
     }
 
@@ -190,6 +426,20 @@ impl<'a, T> CursorMut<'a, T> {
         other.len = 0;
     }
 
+    /// Like [`insert_before`](Self::insert_before), but returns a handle to the new node.
+    pub fn insert_before_handle(&mut self, elem: T) -> NodeHandle<T> {
+        let node = Node::new(elem);
+        self.splice_before(LinkedList::from_chain(node, node, 1));
+        self.list.issue_handle(node)
+    }
+
+    /// Like [`insert_after`](Self::insert_after), but returns a handle to the new node.
+    pub fn insert_after_handle(&mut self, elem: T) -> NodeHandle<T> {
+        let node = Node::new(elem);
+        self.splice_after(LinkedList::from_chain(node, node, 1));
+        self.list.issue_handle(node)
+    }
+
     pub fn insert_before(&mut self, elem: T) {
         let mut other = LinkedList::new();
         other.push_front(elem);
@@ -202,22 +452,135 @@ impl<'a, T> CursorMut<'a, T> {
         self.splice_after(other);
     }
 
+    /// Like [`insert_before`](Self::insert_before), but hands `elem` back instead of aborting
+    /// if allocation fails.
+    pub fn try_insert_before(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let node = Node::try_new(elem)?;
+        self.splice_before(LinkedList::from_chain(node, node, 1));
+        Ok(())
+    }
+
+    /// Like [`insert_after`](Self::insert_after), but hands `elem` back instead of aborting
+    /// if allocation fails.
+    pub fn try_insert_after(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        let node = Node::try_new(elem)?;
+        self.splice_after(LinkedList::from_chain(node, node, 1));
+        Ok(())
+    }
+
     pub fn remove_current(&mut self) -> Option<T> {
         self.cur.map(|cur| unsafe {
-            let node = Box::from_raw(cur.as_ptr());
-            match node.front {
-                Some(front) => (*front.as_ptr()).back = node.back,
-                None => self.list.front = node.back,
+            self.cur = (*cur.as_ptr()).back;
+            self.list.unlink_node(cur).elem
+        })
+    }
+
+    /// Swaps the current node with the one after it, keeping the cursor on the same element.
+    ///
+    /// Nodes are relinked rather than their elements swapped, so element addresses don't change.
+    /// Does nothing on the ghost or the back element.
+    pub fn swap_with_next(&mut self) {
+        let Some(cur) = self.cur else {
+            return;
+        };
+        unsafe {
+            if let Some(next) = (*cur.as_ptr()).back {
+                self.list.detach_node(next);
+                self.list.attach_before(next, Some(cur));
+                self.index += 1;
             }
-            match node.back {
-                Some(back) => (*back.as_ptr()).front = node.front,
-                None => self.list.back = node.front,
+        }
+    }
+
+    /// Swaps the current node with the one before it, keeping the cursor on the same element.
+    ///
+    /// Does nothing on the ghost or the front element.
+    pub fn swap_with_prev(&mut self) {
+        let Some(cur) = self.cur else {
+            return;
+        };
+        unsafe {
+            if let Some(prev) = (*cur.as_ptr()).front {
+                self.list.detach_node(prev);
+                self.list.attach_before(prev, (*cur.as_ptr()).back);
+                self.index -= 1;
             }
-            self.cur = node.back;
-            self.list.len -= 1;
-            node.elem
+        }
+    }
+
+    /// Relinks the current node at the front of the list, keeping the cursor on it.
+    pub fn move_current_to_front(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.list.detach_node(cur);
+                self.list.attach_before(cur, self.list.front);
+            }
+            self.index = 0;
+        }
+    }
+
+    /// Relinks the current node at the back of the list, keeping the cursor on it.
+    pub fn move_current_to_back(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.list.detach_node(cur);
+                self.list.attach_before(cur, None);
+            }
+            self.index = self.list.len - 1;
+        }
+    }
+
+    /// Unlinks the current element and returns it as a one-element list, reusing its node.
+    ///
+    /// Like [`remove_current`](Self::remove_current), the cursor moves on to the next element.
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<T>> {
+        self.cur.map(|cur| unsafe {
+            self.cur = (*cur.as_ptr()).back;
+            self.list.detach_node(cur);
+            self.list.invalidate_handles();
+            LinkedList::from_chain(cur, cur, 1)
         })
     }
+
+    /// Unlinks the current element and up to `n - 1` elements after it as a single list.
+    ///
+    /// Fewer than `n` elements are taken if the list ends first. The cursor moves on to the
+    /// element after the range, and nothing is taken if the cursor is on the ghost.
+    pub fn split_range(&mut self, n: usize) -> LinkedList<T> {
+        let Some(first) = self.cur else {
+            return LinkedList::new();
+        };
+        if n == 0 {
+            return LinkedList::new();
+        }
+        unsafe {
+            let mut last = first;
+            let mut len = 1;
+            while len < n {
+                match (*last.as_ptr()).back {
+                    Some(next) => last = next,
+                    None => break,
+                }
+                len += 1;
+            }
+
+            let before = (*first.as_ptr()).front.take();
+            let after = (*last.as_ptr()).back.take();
+            match before {
+                Some(before) => (*before.as_ptr()).back = after,
+                None => self.list.front = after,
+            }
+            match after {
+                Some(after) => (*after.as_ptr()).front = before,
+                None => self.list.back = before,
+            }
+            self.list.len -= len;
+            self.list.invalidate_handles();
+            self.cur = after;
+
+            LinkedList::from_chain(first, last, len)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -347,7 +710,8 @@ mod tests {
         assert_eq!(cursor.index, 6);
         let tmp = cursor.split_after();
         assert_eq!(cursor.list.len, 7);
-        assert_eq!(tmp.len, 9);
+        assert_eq!(tmp.len, 8);
+        check_links(&tmp);
         assert_eq!(
             tmp.into_iter().collect::<Vec<_>>(),
             &[102, 103, 8, 2, 3, 4, 5, 6]
@@ -359,7 +723,369 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cursor_split_at_ends() {
+        let mut m: LinkedList<u32> = (1..=4).collect();
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        let before = cursor.split_before();
+        assert!(before.is_empty());
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.move_prev();
+        let after = cursor.split_after();
+        assert!(after.is_empty());
+        assert_eq!(cursor.index(), Some(3));
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        let after = cursor.split_after();
+        check_links(&m);
+        check_links(&after);
+        assert_eq!(m.into_iter().collect::<Vec<_>>(), &[1]);
+        assert_eq!(after.len, 3);
+        assert_eq!(after.into_iter().collect::<Vec<_>>(), &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        let m: LinkedList<u32> = (1..=4).collect();
+        let mut cursor = m.cursor();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&4));
+
+        let mut front = m.cursor_front();
+        let back = m.cursor_back();
+        cursor.move_next();
+        assert_eq!(cursor.current(), front.current());
+        assert_eq!(front.current(), Some(&1));
+        assert_eq!(front.index(), Some(0));
+        assert_eq!(back.current(), Some(&4));
+        assert_eq!(back.index(), Some(3));
+        assert_eq!(back.peek_next(), None);
+        assert_eq!(back.peek_prev(), Some(&3));
+
+        let saved = front;
+        front.move_next();
+        front.move_next();
+        assert_eq!(front.current(), Some(&3));
+        assert_eq!(front.index(), Some(2));
+        assert_eq!(saved.current(), Some(&1));
+        front.move_prev();
+        assert_eq!(front.current(), Some(&2));
+        assert_eq!(front.peek_prev(), Some(&1));
+        assert_eq!(front.peek_next(), Some(&3));
+
+        let empty: LinkedList<u32> = LinkedList::new();
+        assert_eq!(empty.cursor_front().current(), None);
+        assert_eq!(empty.cursor_back().index(), None);
+    }
+
+    #[test]
+    fn test_cursor_front_back_mut() {
+        let mut m: LinkedList<u32> = (1..=4).collect();
+        let mut cursor = m.cursor_front_mut();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.as_cursor().peek_next(), Some(&2));
+        *cursor.current().unwrap() = 10;
+
+        let mut cursor = m.cursor_back_mut();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 2, 3]);
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        assert_eq!(empty.cursor_front_mut().current(), None);
+        assert_eq!(empty.cursor_back_mut().index(), None);
+    }
+
+    #[test]
+    fn test_cursor_seek_to() {
+        let mut m: LinkedList<u32> = (0..10).collect();
+        let mut cursor = m.cursor_mut();
+        for index in [0, 9, 5, 6, 4, 10, 1, 8, 2, 10, 10, 3, 3, 7] {
+            cursor.seek_to(index);
+            if index == 10 {
+                assert_eq!(cursor.index(), None);
+                assert_eq!(cursor.current(), None);
+            } else {
+                assert_eq!(cursor.index(), Some(index));
+                assert_eq!(cursor.current(), Some(&mut (index as u32)));
+            }
+        }
+
+        for start in 0..=10 {
+            for index in 0..=10 {
+                let mut cursor = m.cursor_mut();
+                cursor.seek_to(start);
+                cursor.seek_to(index);
+                assert_eq!(cursor.index(), (index < 10).then_some(index));
+                cursor.move_next();
+                let expected = match index {
+                    9 => None,
+                    10 => Some(0),
+                    _ => Some(index + 1),
+                };
+                assert_eq!(cursor.index(), expected);
+            }
+        }
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        cursor.seek_to(0);
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_seek_to_out_of_bounds() {
+        let mut m: LinkedList<u32> = (0..10).collect();
+        m.cursor_mut().seek_to(11);
+    }
+
+    #[test]
+    fn test_cursor_move_by() {
+        let mut m: LinkedList<u32> = (0..4).collect();
+        let mut cursor = m.cursor_mut();
+        assert!(cursor.move_by(0));
+        assert!(!cursor.move_by(1));
+        assert_eq!(cursor.index(), Some(0));
+        assert!(!cursor.move_by(3));
+        assert_eq!(cursor.index(), Some(3));
+        assert!(cursor.move_by(1));
+        assert!(!cursor.move_by(1));
+        assert_eq!(cursor.index(), Some(0));
+        assert!(cursor.move_by(-1));
+        assert!(!cursor.move_by(-1));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert!(!cursor.move_by(-3));
+        assert_eq!(cursor.index(), Some(0));
+        assert!(!cursor.move_by(10));
+        assert_eq!(cursor.index(), Some(0));
+        assert!(!cursor.move_by(-12));
+        assert_eq!(cursor.index(), Some(3));
+        assert!(!cursor.move_by(0));
+        assert_eq!(cursor.index(), Some(3));
+        assert!(cursor.move_by(isize::MAX - 1));
+        assert!(!cursor.move_by(isize::MIN));
+        assert_eq!(cursor.index(), Some(1));
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        assert!(cursor.move_by(1));
+        assert!(cursor.move_by(-1));
+        assert!(cursor.move_by(isize::MIN));
+    }
+
+    #[test]
+    fn test_cursor_mut_find() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut cursor = m.cursor_mut();
+        assert_eq!(cursor.find_next(|x| x % 3 == 0), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.find_next(|x| x % 3 == 0), Some(&mut 6));
+        assert_eq!(cursor.index(), Some(5));
+        cursor.insert_before(0);
+        assert_eq!(cursor.find_prev(|x| *x == 0), Some(&mut 0));
+        assert_eq!(cursor.index(), Some(5));
+        assert_eq!(cursor.find_prev(|x| *x > 2), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.find_next(|x| *x > 10), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.find_prev(|x| x % 2 == 0), Some(&mut 8));
+        assert_eq!(cursor.index(), Some(8));
+        assert_eq!(cursor.find_prev(|x| *x > 10), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.find_next(|_| true), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(0));
+        check_links(&m);
+
+        let mut empty: LinkedList<u32> = LinkedList::new();
+        let mut cursor = empty.cursor_mut();
+        assert_eq!(cursor.find_next(|_| true), None);
+        assert_eq!(cursor.find_prev(|_| true), None);
+    }
+
+    #[test]
+    fn test_cursor_find() {
+        let m: LinkedList<u32> = (1..=8).collect();
+        let mut cursor = m.cursor();
+        assert_eq!(cursor.find_next(|x| x % 3 == 0), Some(&3));
+        assert_eq!(cursor.index(), Some(2));
+        let mut back = cursor;
+        assert_eq!(back.find_prev(|x| x % 3 == 0), None);
+        assert_eq!(back.index(), None);
+        assert_eq!(back.find_prev(|x| x % 3 == 0), Some(&6));
+        assert_eq!(back.index(), Some(5));
+        assert_eq!(cursor.find_next(|x| *x > 6), Some(&7));
+        assert_eq!(cursor.index(), Some(6));
+        assert_eq!(cursor.find_next(|x| *x < 6), None);
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_remove_current_as_list() {
+        let mut m: LinkedList<u32> = (1..=4).collect();
+        let mut cursor = m.cursor_mut();
+        assert!(cursor.remove_current_as_list().is_none());
+        cursor.move_next();
+        cursor.move_next();
+        let addr = cursor.current().map(|x| x as *mut u32);
+        let mut removed = cursor.remove_current_as_list().unwrap();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(removed.len, 1);
+        assert_eq!(removed.front_mut().map(|x| x as *mut u32), addr);
+        check_links(&removed);
+        assert_eq!(removed.into_iter().collect::<Vec<_>>(), &[2]);
+
+        let mut cursor = m.cursor_back_mut();
+        let removed = cursor.remove_current_as_list().unwrap();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(removed.into_iter().collect::<Vec<_>>(), &[4]);
+        check_links(&m);
+        assert_eq!(m.len, 2);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 3]);
+    }
+
+    #[test]
+    fn test_cursor_split_range() {
+        let mut m: LinkedList<u32> = (0..10).collect();
+        let mut cursor = m.cursor_mut();
+        assert!(cursor.split_range(3).is_empty());
+        cursor.seek_to(2);
+        assert!(cursor.split_range(0).is_empty());
+        let range = cursor.split_range(3);
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(range.len, 3);
+        check_links(&range);
+        assert_eq!(range.into_iter().collect::<Vec<_>>(), &[2, 3, 4]);
+
+        cursor.seek_to(0);
+        let range = cursor.split_range(1);
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(range.into_iter().collect::<Vec<_>>(), &[0]);
+
+        cursor.seek_to(3);
+        let range = cursor.split_range(10);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(range.len, 3);
+        check_links(&range);
+        assert_eq!(range.into_iter().collect::<Vec<_>>(), &[7, 8, 9]);
+        check_links(&m);
+        assert_eq!(m.len, 3);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 5, 6]);
+
+        let range = m.cursor_front_mut().split_range(3);
+        assert!(m.is_empty());
+        assert_eq!(m.front, None);
+        assert_eq!(m.back, None);
+        assert_eq!(range.into_iter().collect::<Vec<_>>(), &[1, 5, 6]);
+    }
+
+    #[test]
+    fn test_cursor_swap() {
+        let mut m: LinkedList<u32> = (0..5).collect();
+        let mut cursor = m.cursor_mut();
+        cursor.swap_with_next();
+        cursor.swap_with_prev();
+        assert_eq!(cursor.index(), None);
+
+        cursor.move_next();
+        let addr = cursor.current().map(|x| x as *mut u32);
+        cursor.swap_with_prev();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.swap_with_next();
+        assert_eq!(cursor.index(), Some(1));
+        cursor.swap_with_next();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current().map(|x| x as *mut u32), addr);
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 0, 3, 4]);
+
+        let mut cursor = m.cursor_back_mut();
+        cursor.swap_with_next();
+        assert_eq!(cursor.index(), Some(4));
+        cursor.swap_with_prev();
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 4));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 0, 4, 3]);
+        assert_eq!(m.back(), Some(&3));
+
+        let mut two: LinkedList<u32> = (0..2).collect();
+        two.cursor_back_mut().swap_with_prev();
+        check_links(&two);
+        assert_eq!(two.front(), Some(&1));
+        assert_eq!(two.back(), Some(&0));
+    }
+
+    #[test]
+    fn test_cursor_move_current_to_ends() {
+        let mut m: LinkedList<u32> = (0..5).collect();
+        let mut cursor = m.cursor_mut();
+        cursor.move_current_to_front();
+        cursor.move_current_to_back();
+        assert_eq!(cursor.index(), None);
+
+        cursor.seek_to(2);
+        cursor.move_current_to_front();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        cursor.move_next();
+        cursor.move_current_to_back();
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.peek_next(), None);
+        cursor.move_current_to_back();
+        assert_eq!(cursor.index(), Some(4));
+        cursor.seek_to(0);
+        cursor.move_current_to_front();
+        assert_eq!(cursor.index(), Some(0));
+        check_links(&m);
+        assert_eq!(m.len, 5);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[2, 1, 3, 4, 0]);
+    }
+
+    #[test]
+    fn test_cursor_insert_handle() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        let mut cursor = m.cursor_mut();
+        let two = cursor.insert_before_handle(2);
+        let zero = cursor.insert_after_handle(0);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        let one = cursor.insert_after_handle(1);
+        let three = cursor.insert_before_handle(3);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[3, 0, 1, 2]);
+        assert!(m.move_to_back(&zero));
+        assert_eq!(m.remove_handle(&three), Some(3));
+        assert_eq!(m.get_handle(&one), Some(&1));
+        assert_eq!(m.get_handle(&two), Some(&2));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 0]);
+
+        let mut cursor = m.cursor_front_mut();
+        let split = cursor.split_after();
+        assert_eq!(m.get_handle(&one), None);
+        assert_eq!(split.get_handle(&two), None);
+    }
+
     fn check_links<T: Eq + std::fmt::Debug>(list: &LinkedList<T>) {
+        #[cfg(feature = "debug-invariants")]
+        list.validate().unwrap();
+
         let mut last = None;
         let mut maybe_node = list.front;
         while let Some(node) = maybe_node {
@@ -376,4 +1102,41 @@ mod tests {
 
         assert_eq!(from_front, re_reved);
     }
+
+    #[cfg(feature = "debug-invariants")]
+    #[test]
+    fn test_validate() {
+        use crate::error::InvariantError;
+
+        let mut list: LinkedList<_> = (0..5).collect();
+        let mut other: LinkedList<_> = (0..2).collect();
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.validate(), Ok(()));
+        cursor.seek_to(3);
+        assert_eq!(cursor.validate(), Ok(()));
+
+        cursor.index = 1;
+        assert_eq!(
+            cursor.validate(),
+            Err(InvariantError::CursorIndexMismatch {
+                index: 1,
+                actual: 3
+            })
+        );
+        cursor.index = 3;
+
+        let cur = cursor.cur.replace(other.front.unwrap());
+        assert_eq!(cursor.validate(), Err(InvariantError::CursorNotInList));
+        cursor.cur = cur;
+        assert_eq!(cursor.validate(), Ok(()));
+
+        // the list is checked too
+        cursor.list.len = 4;
+        assert_eq!(
+            cursor.validate(),
+            Err(InvariantError::LenMismatch { len: 4, actual: 5 })
+        );
+        cursor.list.len = 5;
+        other.clear();
+    }
 }
@@ -0,0 +1,252 @@
+use std::{
+    fmt::{self, Debug},
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use super::{LinkedList, Node};
+
+/// A [`LinkedList`] that keeps the allocations of popped nodes around and reuses them for
+/// later pushes, so steady push/pop workloads stop hitting the allocator.
+///
+/// `push_*`, `pop_*` and `clear` go through the pool. Everything else derefs to the plain
+/// list, which allocates and frees nodes as usual.
+pub struct PooledLinkedList<T> {
+    list: LinkedList<T>,
+    free: Vec<Box<MaybeUninit<Node<T>>>>,
+}
+
+impl<T> PooledLinkedList<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            list: LinkedList::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Creates an empty list with `n` nodes already allocated.
+    #[must_use]
+    pub fn with_node_cache(n: usize) -> Self {
+        let mut list = Self::new();
+        list.reserve_nodes(n);
+        list
+    }
+
+    /// Makes sure at least `additional` more elements can be pushed without allocating.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        let missing = additional.saturating_sub(self.free.len());
+        self.free.reserve(missing);
+        self.free
+            .extend((0..missing).map(|_| Box::new(MaybeUninit::uninit())));
+    }
+
+    /// Frees every cached node.
+    pub fn shrink_to_fit(&mut self) {
+        self.free = Vec::new();
+    }
+
+    /// Returns how many nodes are cached for reuse.
+    #[must_use]
+    pub fn cached_nodes(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        let node = self.node(elem);
+        unsafe { self.list.attach_before(node, self.list.front) };
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        let node = self.node(elem);
+        unsafe { self.list.attach_before(node, None) };
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.list.front?;
+        Some(unsafe { self.recycle(node) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let node = self.list.back?;
+        Some(unsafe { self.recycle(node) })
+    }
+
+    /// Removes every element, keeping their nodes for reuse.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Unwraps the list, freeing the cached nodes.
+    #[must_use]
+    pub fn into_inner(self) -> LinkedList<T> {
+        self.list
+    }
+
+    fn node(&mut self, elem: T) -> NonNull<Node<T>> {
+        let node = Node {
+            front: None,
+            back: None,
+            elem,
+        };
+        let node = match self.free.pop() {
+            Some(mut slot) => {
+                slot.write(node);
+                Box::into_raw(slot).cast::<Node<T>>()
+            }
+            None => Box::into_raw(Box::new(node)),
+        };
+        unsafe { NonNull::new_unchecked(node) }
+    }
+
+    /// Detaches `node`, moves its element out and puts the allocation on the free list.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    unsafe fn recycle(&mut self, node: NonNull<Node<T>>) -> T {
+        self.list.detach_node(node);
+        // the allocation outlives the element, so its handle has to go now
+        self.list.forget_handle(node);
+        let node = node.as_ptr();
+        let elem = ptr::read(&(*node).elem);
+        self.free
+            .push(Box::from_raw(node.cast::<MaybeUninit<Node<T>>>()));
+        elem
+    }
+}
+
+impl<T> Deref for PooledLinkedList<T> {
+    type Target = LinkedList<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.list
+    }
+}
+
+impl<T> DerefMut for PooledLinkedList<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.list
+    }
+}
+
+impl<T> Default for PooledLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<LinkedList<T>> for PooledLinkedList<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        Self {
+            list,
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<T> Extend<T> for PooledLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for PooledLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T: Debug> Debug for PooledLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+unsafe impl<T: Send> Send for PooledLinkedList<T> {}
+unsafe impl<T: Sync> Sync for PooledLinkedList<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::PooledLinkedList;
+
+    #[test]
+    fn reuses_nodes() {
+        let mut list = PooledLinkedList::with_node_cache(4);
+        assert_eq!(list.cached_nodes(), 4);
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list.cached_nodes(), 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.cached_nodes(), 3);
+        for i in 0..3 {
+            list.push_back(i);
+        }
+        assert_eq!(list.cached_nodes(), 0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[2, 0, 1, 2]);
+
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(list.cached_nodes(), 4);
+        list.reserve_nodes(2);
+        assert_eq!(list.cached_nodes(), 4);
+        list.reserve_nodes(6);
+        assert_eq!(list.cached_nodes(), 6);
+        list.shrink_to_fit();
+        assert_eq!(list.cached_nodes(), 0);
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn mixes_with_plain_list_operations() {
+        let mut list: PooledLinkedList<_> = (0..5).collect();
+        list.insert(2, 10);
+        list.retain(|&x| x != 3);
+        list.sort();
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(10));
+        assert_eq!(list.cached_nodes(), 2);
+        assert_eq!(
+            list.into_inner().into_iter().collect::<Vec<_>>(),
+            &[1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn drops_elements_once() {
+        let tracker = Rc::new(());
+        let mut list = PooledLinkedList::new();
+        for _ in 0..4 {
+            list.push_back(tracker.clone());
+        }
+        drop(list.pop_front());
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        list.push_front(tracker.clone());
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn recycling_kills_handles() {
+        let mut list = PooledLinkedList::new();
+        let handle = list.push_back_handle('a');
+        list.push_back('b');
+        assert_eq!(list.pop_front(), Some('a'));
+        list.push_front('c');
+        assert_eq!(list.get_handle(&handle), None);
+        assert_eq!(list.remove_handle(&handle), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &['c', 'b']);
+    }
+}
@@ -0,0 +1,118 @@
+//! Checks that the `try_*` insertion methods survive allocation failure, using a global
+//! allocator that can be told to fail every allocation on the current thread.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    ptr,
+};
+
+use too_many_lists::{fifth, second, sixth::LinkedList};
+
+struct FailingAlloc;
+
+thread_local! {
+    static FAIL: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for FailingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if FAIL.try_with(Cell::get).unwrap_or(false) {
+            ptr::null_mut()
+        } else {
+            System.alloc(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: FailingAlloc = FailingAlloc;
+
+/// Runs `f` with every allocation on this thread failing.
+fn out_of_memory<R>(f: impl FnOnce() -> R) -> R {
+    FAIL.with(|fail| fail.set(true));
+    let result = f();
+    FAIL.with(|fail| fail.set(false));
+    result
+}
+
+#[test]
+fn sixth() {
+    let mut list = LinkedList::new();
+    list.try_push_back(String::from("b")).unwrap();
+    list.try_push_front(String::from("a")).unwrap();
+    list.try_insert(2, String::from("d")).unwrap();
+
+    // the elements are allocated up front, so only the nodes fail
+    let (x, y, z) = (String::from("x"), String::from("y"), String::from("z"));
+    let (front, back, insert) = out_of_memory(|| {
+        (
+            list.try_push_front(x),
+            list.try_push_back(y),
+            list.try_insert(1, z),
+        )
+    });
+    assert_eq!(front.unwrap_err().into_inner(), "x");
+    assert_eq!(back.unwrap_err().into_inner(), "y");
+    let err = insert.unwrap_err();
+    assert_eq!(err.to_string(), "memory allocation failed");
+    assert_eq!(err.into_inner(), "z");
+    assert_eq!(list.len(), 3);
+
+    list.try_insert(2, String::from("c")).unwrap();
+    assert_eq!(list.iter().collect::<Vec<_>>(), &["a", "b", "c", "d"]);
+}
+
+#[test]
+fn sixth_cursor() {
+    let mut list: LinkedList<_> = (1..=3).collect();
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    let (before, after) =
+        out_of_memory(|| (cursor.try_insert_before(10), cursor.try_insert_after(20)));
+    assert_eq!(before.unwrap_err().into_inner(), 10);
+    assert_eq!(after.unwrap_err().into_inner(), 20);
+    assert_eq!(cursor.index(), Some(1));
+
+    cursor.try_insert_before(10).unwrap();
+    cursor.try_insert_after(20).unwrap();
+    assert_eq!(cursor.index(), Some(2));
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.try_insert_before(30).unwrap();
+    cursor.try_insert_after(0).unwrap();
+    assert_eq!(
+        list.into_iter().collect::<Vec<_>>(),
+        &[0, 1, 10, 2, 20, 3, 30]
+    );
+}
+
+#[test]
+fn second() {
+    let mut list = second::List::new();
+    list.try_push(1).unwrap();
+    let err = out_of_memory(|| list.try_push(2)).unwrap_err();
+    assert_eq!(err.into_inner(), 2);
+    list.try_push(3).unwrap();
+    assert_eq!(list.pop(), Some(3));
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), None);
+}
+
+#[test]
+fn fifth() {
+    let mut list = fifth::List::new();
+    list.try_push(1).unwrap();
+    let err = out_of_memory(|| list.try_push(2)).unwrap_err();
+    assert_eq!(err.into_inner(), 2);
+    list.try_push(3).unwrap();
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop(), Some(3));
+    assert_eq!(list.pop(), None);
+}
@@ -0,0 +1,619 @@
+//! Drives the lists with random operation sequences and compares every step against a
+//! `VecDeque` model.
+//!
+//! Runs are deterministic: each case is generated from its seed. When a case fails, the
+//! sequence is shrunk to the shortest failing prefix, which is printed along with the seed.
+//! Set `DIFF_SEED` to rerun a single seed, and `DIFF_CASES` to change how many are run.
+
+use std::{
+    collections::VecDeque,
+    env,
+    fmt::Debug,
+    mem,
+    panic::{self, AssertUnwindSafe},
+};
+
+use too_many_lists::{fifth, fourth, second, sixth::LinkedList};
+
+const CASES: u64 = 200;
+const OPS: usize = 300;
+
+/// SplitMix64, which is plenty for picking operations.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    fn index(&mut self) -> usize {
+        self.below(64) as usize
+    }
+
+    fn value(&mut self) -> u8 {
+        self.below(16) as u8
+    }
+}
+
+type Outcome = Result<(), String>;
+
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr, $($what:tt)+) => {{
+        let (left, right) = (&$left, &$right);
+        if left != right {
+            return Err(format!("{}: list has {left:?}, model has {right:?}", format!($($what)+)));
+        }
+    }};
+}
+
+/// Runs `run` on `ops`, turning a panic into a failure.
+fn outcome<Op>(run: fn(&[Op]) -> Outcome, ops: &[Op]) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| run(ops))).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        Err(format!("panicked: {msg}"))
+    })
+}
+
+/// Runs every case for one list, and panics with the shortest failing prefix of the first
+/// failing case.
+fn check<Op: Debug>(name: &str, generate: fn(&mut Rng) -> Op, run: fn(&[Op]) -> Outcome) {
+    let seeds = match env::var("DIFF_SEED") {
+        Ok(seed) => {
+            let seed = seed.parse().expect("DIFF_SEED should be a u64");
+            seed..seed + 1
+        }
+        Err(_) => {
+            let cases = env::var("DIFF_CASES").map_or(CASES, |cases| {
+                cases.parse().expect("DIFF_CASES should be a u64")
+            });
+            0..cases
+        }
+    };
+
+    for seed in seeds {
+        let mut rng = Rng::new(seed);
+        let ops: Vec<_> = (0..OPS).map(|_| generate(&mut rng)).collect();
+        if outcome(run, &ops).is_ok() {
+            continue;
+        }
+
+        // a failing run keeps failing when more ops are appended, so binary search for the
+        // shortest failing prefix
+        let (mut passing, mut failing) = (0, ops.len());
+        while failing - passing > 1 {
+            let mid = passing + (failing - passing) / 2;
+            if outcome(run, &ops[..mid]).is_ok() {
+                passing = mid;
+            } else {
+                failing = mid;
+            }
+        }
+        let err = outcome(run, &ops[..failing]).unwrap_err();
+        panic!(
+            "{name}: seed {seed} fails after {failing} ops: {err}\n\
+             ops: {:#?}\n\
+             rerun with DIFF_SEED={seed}",
+            &ops[..failing]
+        );
+    }
+}
+
+#[derive(Debug)]
+enum SixthOp {
+    PushFront(u8),
+    PushBack(u8),
+    PopFront,
+    PopBack,
+    Insert(usize, u8),
+    Remove(usize),
+    Get(usize),
+    /// `split_off`, then put the pieces back together either way round.
+    SplitOff(usize, bool),
+    Drain(usize, usize),
+    Retain(u8),
+    Rotate(usize, bool),
+    Reverse,
+    Sort,
+    Dedup,
+    Clone,
+    Cursor(CursorOp),
+}
+
+#[derive(Debug)]
+enum CursorOp {
+    MoveNext,
+    MovePrev,
+    Seek(usize),
+    InsertBefore(u8),
+    InsertAfter(u8),
+    RemoveCurrent,
+    SplitBefore,
+    SplitAfter,
+    SwapWithNext,
+    SwapWithPrev,
+}
+
+fn sixth_op(rng: &mut Rng) -> SixthOp {
+    match rng.below(40) {
+        0..=4 => SixthOp::PushFront(rng.value()),
+        5..=9 => SixthOp::PushBack(rng.value()),
+        10 | 11 => SixthOp::PopFront,
+        12 | 13 => SixthOp::PopBack,
+        14 | 15 => SixthOp::Insert(rng.index(), rng.value()),
+        16 => SixthOp::Remove(rng.index()),
+        17 => SixthOp::Get(rng.index()),
+        18 => SixthOp::SplitOff(rng.index(), rng.below(2) == 0),
+        19 => SixthOp::Drain(rng.index(), rng.index()),
+        20 => SixthOp::Retain(rng.value()),
+        21 => SixthOp::Rotate(rng.index(), rng.below(2) == 0),
+        22 => SixthOp::Reverse,
+        23 => SixthOp::Sort,
+        24 => SixthOp::Dedup,
+        25 => SixthOp::Clone,
+        _ => SixthOp::Cursor(match rng.below(14) {
+            0 | 1 => CursorOp::MoveNext,
+            2 | 3 => CursorOp::MovePrev,
+            4 => CursorOp::Seek(rng.index()),
+            5 | 6 => CursorOp::InsertBefore(rng.value()),
+            7 | 8 => CursorOp::InsertAfter(rng.value()),
+            9 => CursorOp::RemoveCurrent,
+            10 => CursorOp::SplitBefore,
+            11 => CursorOp::SplitAfter,
+            12 => CursorOp::SwapWithNext,
+            _ => CursorOp::SwapWithPrev,
+        }),
+    }
+}
+
+fn same_elements(list: &LinkedList<u8>, model: &VecDeque<u8>, what: &str) -> Outcome {
+    ensure_eq!(list.len(), model.len(), "{what} len");
+    ensure_eq!(
+        list.iter().collect::<Vec<_>>(),
+        model.iter().collect::<Vec<_>>(),
+        "{what} elements"
+    );
+    ensure_eq!(
+        list.iter().rev().collect::<Vec<_>>(),
+        model.iter().rev().collect::<Vec<_>>(),
+        "{what} elements from the back"
+    );
+    ensure_eq!(list.front(), model.front(), "{what} front");
+    ensure_eq!(list.back(), model.back(), "{what} back");
+    #[cfg(feature = "debug-invariants")]
+    list.validate().map_err(|err| format!("{what}: {err}"))?;
+    Ok(())
+}
+
+fn run_sixth(ops: &[SixthOp]) -> Outcome {
+    let mut list = LinkedList::new();
+    let mut model = VecDeque::new();
+    // where cursor ops put the cursor, with `model.len()` standing for the ghost; other ops
+    // leave it at the same index, or on the ghost if the list got shorter than that
+    let mut pos = 0;
+
+    for (step, op) in ops.iter().enumerate() {
+        let len = model.len();
+        match *op {
+            SixthOp::PushFront(elem) => {
+                list.push_front(elem);
+                model.push_front(elem);
+            }
+            SixthOp::PushBack(elem) => {
+                list.push_back(elem);
+                model.push_back(elem);
+            }
+            SixthOp::PopFront => {
+                ensure_eq!(
+                    list.pop_front(),
+                    model.pop_front(),
+                    "step {step}: pop_front"
+                );
+            }
+            SixthOp::PopBack => {
+                ensure_eq!(list.pop_back(), model.pop_back(), "step {step}: pop_back");
+            }
+            SixthOp::Insert(index, elem) => {
+                let index = index % (len + 1);
+                list.insert(index, elem);
+                model.insert(index, elem);
+            }
+            SixthOp::Remove(index) => {
+                let index = index % (len + 2);
+                ensure_eq!(
+                    list.remove(index),
+                    model.remove(index),
+                    "step {step}: remove({index})"
+                );
+            }
+            SixthOp::Get(index) => {
+                let index = index % (len + 2);
+                ensure_eq!(
+                    list.get(index),
+                    model.get(index),
+                    "step {step}: get({index})"
+                );
+                if let Some(elem) = list.get_mut(index) {
+                    *elem = elem.wrapping_add(1);
+                    model[index] = model[index].wrapping_add(1);
+                }
+            }
+            SixthOp::SplitOff(at, to_front) => {
+                let at = at % (len + 1);
+                let mut tail = list.split_off(at);
+                let model_tail = model.split_off(at);
+                same_elements(
+                    &tail,
+                    &model_tail,
+                    &format!("step {step}: split_off({at}) tail"),
+                )?;
+                same_elements(&list, &model, &format!("step {step}: split_off({at}) head"))?;
+                if to_front {
+                    list.prepend(&mut tail);
+                    for elem in model_tail.into_iter().rev() {
+                        model.push_front(elem);
+                    }
+                } else {
+                    list.append(&mut tail);
+                    model.extend(model_tail);
+                }
+                ensure_eq!(tail.len(), 0, "step {step}: emptied list len");
+            }
+            SixthOp::Drain(start, end) => {
+                let (start, end) = (start % (len + 1), end % (len + 1));
+                let (start, end) = (start.min(end), start.max(end));
+                ensure_eq!(
+                    list.drain(start..end).collect::<Vec<_>>(),
+                    model.drain(start..end).collect::<Vec<_>>(),
+                    "step {step}: drain({start}..{end})"
+                );
+            }
+            SixthOp::Retain(modulo) => {
+                let keep = |&elem: &u8| elem % 4 != modulo % 4;
+                list.retain(keep);
+                model.retain(keep);
+            }
+            SixthOp::Rotate(n, left) => {
+                if len > 0 {
+                    let n = n % len;
+                    if left {
+                        list.rotate_left(n);
+                        model.rotate_left(n);
+                    } else {
+                        list.rotate_right(n);
+                        model.rotate_right(n);
+                    }
+                }
+            }
+            SixthOp::Reverse => {
+                list.reverse();
+                model.make_contiguous().reverse();
+            }
+            SixthOp::Sort => {
+                list.sort();
+                model.make_contiguous().sort();
+            }
+            SixthOp::Dedup => {
+                list.dedup();
+                let mut deduped: Vec<_> = model.drain(..).collect();
+                deduped.dedup();
+                model.extend(deduped);
+            }
+            SixthOp::Clone => {
+                let clone = list.clone();
+                same_elements(&clone, &model, &format!("step {step}: clone"))?;
+                list = clone;
+            }
+            SixthOp::Cursor(ref op) => {
+                run_cursor_op(&mut list, &mut model, &mut pos, op)
+                    .map_err(|err| format!("step {step}: {op:?}: {err}"))?;
+            }
+        }
+        same_elements(&list, &model, &format!("after step {step} ({op:?})"))?;
+        pos = pos.min(model.len());
+    }
+    Ok(())
+}
+
+fn run_cursor_op(
+    list: &mut LinkedList<u8>,
+    model: &mut VecDeque<u8>,
+    pos: &mut usize,
+    op: &CursorOp,
+) -> Outcome {
+    let len = model.len();
+    let mut cursor = list.cursor_mut();
+    cursor.seek_to(*pos);
+    match *op {
+        CursorOp::MoveNext => {
+            cursor.move_next();
+            *pos = (*pos + 1) % (len + 1);
+        }
+        CursorOp::MovePrev => {
+            cursor.move_prev();
+            *pos = (*pos + len) % (len + 1);
+        }
+        CursorOp::Seek(index) => {
+            *pos = index % (len + 1);
+            cursor.seek_to(*pos);
+        }
+        CursorOp::InsertBefore(elem) => {
+            cursor.insert_before(elem);
+            model.insert(*pos, elem);
+            *pos += 1;
+        }
+        CursorOp::InsertAfter(elem) => {
+            cursor.insert_after(elem);
+            if *pos == len {
+                model.push_front(elem);
+                *pos += 1;
+            } else {
+                model.insert(*pos + 1, elem);
+            }
+        }
+        CursorOp::RemoveCurrent => {
+            ensure_eq!(cursor.remove_current(), model.remove(*pos), "removed");
+        }
+        CursorOp::SplitBefore => {
+            let split = cursor.split_before();
+            let expected: VecDeque<_> = if *pos == len {
+                mem::take(model)
+            } else {
+                model.drain(..*pos).collect()
+            };
+            same_elements(&split, &expected, "split off part")?;
+            *pos = 0;
+        }
+        CursorOp::SplitAfter => {
+            let split = cursor.split_after();
+            let expected: VecDeque<_> = if *pos == len {
+                mem::take(model)
+            } else {
+                model.drain(*pos + 1..).collect()
+            };
+            same_elements(&split, &expected, "split off part")?;
+            if *pos == len {
+                *pos = 0;
+            }
+        }
+        CursorOp::SwapWithNext => {
+            cursor.swap_with_next();
+            if *pos + 1 < len {
+                model.swap(*pos, *pos + 1);
+                *pos += 1;
+            }
+        }
+        CursorOp::SwapWithPrev => {
+            cursor.swap_with_prev();
+            if *pos > 0 && *pos < len {
+                model.swap(*pos - 1, *pos);
+                *pos -= 1;
+            }
+        }
+    }
+
+    let expected_index = (*pos < model.len()).then_some(*pos);
+    ensure_eq!(cursor.index(), expected_index, "cursor index");
+    ensure_eq!(
+        cursor.current().copied(),
+        model.get(*pos).copied(),
+        "cursor current"
+    );
+    #[cfg(feature = "debug-invariants")]
+    cursor.validate().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+#[test]
+fn sixth() {
+    check("sixth::LinkedList", sixth_op, run_sixth);
+}
+
+#[derive(Debug)]
+enum DequeOp {
+    PushFront(u8),
+    PushBack(u8),
+    PopFront,
+    PopBack,
+    BumpFront,
+    BumpBack,
+}
+
+fn deque_op(rng: &mut Rng) -> DequeOp {
+    match rng.below(10) {
+        0 | 1 => DequeOp::PushFront(rng.value()),
+        2 | 3 => DequeOp::PushBack(rng.value()),
+        4 | 5 => DequeOp::PopFront,
+        6 | 7 => DequeOp::PopBack,
+        8 => DequeOp::BumpFront,
+        _ => DequeOp::BumpBack,
+    }
+}
+
+fn run_fourth(ops: &[DequeOp]) -> Outcome {
+    let mut list = fourth::List::new();
+    let mut model = VecDeque::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        match *op {
+            DequeOp::PushFront(elem) => {
+                list.push_front(elem);
+                model.push_front(elem);
+            }
+            DequeOp::PushBack(elem) => {
+                list.push_back(elem);
+                model.push_back(elem);
+            }
+            DequeOp::PopFront => {
+                ensure_eq!(
+                    list.pop_front(),
+                    model.pop_front(),
+                    "step {step}: pop_front"
+                );
+            }
+            DequeOp::PopBack => {
+                ensure_eq!(list.pop_back(), model.pop_back(), "step {step}: pop_back");
+            }
+            DequeOp::BumpFront => {
+                if let Some(mut elem) = list.peek_front_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+                if let Some(elem) = model.front_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+            }
+            DequeOp::BumpBack => {
+                if let Some(mut elem) = list.peek_back_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+                if let Some(elem) = model.back_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+            }
+        }
+        ensure_eq!(
+            list.peek_front().map(|elem| *elem),
+            model.front().copied(),
+            "after step {step} ({op:?}): front"
+        );
+        ensure_eq!(
+            list.peek_back().map(|elem| *elem),
+            model.back().copied(),
+            "after step {step} ({op:?}): back"
+        );
+    }
+
+    ensure_eq!(
+        list.into_iter().rev().collect::<Vec<_>>(),
+        model.into_iter().rev().collect::<Vec<_>>(),
+        "remaining elements"
+    );
+    Ok(())
+}
+
+#[test]
+fn fourth() {
+    check("fourth::List", deque_op, run_fourth);
+}
+
+#[derive(Debug)]
+enum StackOp {
+    Push(u8),
+    TryPush(u8),
+    Pop,
+    Bump,
+}
+
+fn stack_op(rng: &mut Rng) -> StackOp {
+    match rng.below(8) {
+        0..=2 => StackOp::Push(rng.value()),
+        3 => StackOp::TryPush(rng.value()),
+        4..=6 => StackOp::Pop,
+        _ => StackOp::Bump,
+    }
+}
+
+/// `fifth::List` is a queue: push at the back, pop from the front.
+fn run_fifth(ops: &[StackOp]) -> Outcome {
+    let mut list = fifth::List::new();
+    let mut model = VecDeque::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        match *op {
+            StackOp::Push(elem) => list.push(elem),
+            StackOp::TryPush(elem) => list
+                .try_push(elem)
+                .map_err(|err| format!("step {step}: try_push: {err}"))?,
+            StackOp::Pop => {
+                ensure_eq!(list.pop(), model.pop_front(), "step {step}: pop");
+            }
+            StackOp::Bump => {
+                if let Some(elem) = list.peek_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+                if let Some(elem) = model.front_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+            }
+        }
+        if let StackOp::Push(elem) | StackOp::TryPush(elem) = *op {
+            model.push_back(elem);
+        }
+        ensure_eq!(
+            list.peek(),
+            model.front(),
+            "after step {step} ({op:?}): peek"
+        );
+        ensure_eq!(
+            list.iter().collect::<Vec<_>>(),
+            model.iter().collect::<Vec<_>>(),
+            "after step {step} ({op:?}): elements"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn fifth() {
+    check("fifth::List", stack_op, run_fifth);
+}
+
+/// `second::List` is a stack: push and pop at the front.
+fn run_second(ops: &[StackOp]) -> Outcome {
+    let mut list = second::List::new();
+    let mut model = VecDeque::new();
+
+    for (step, op) in ops.iter().enumerate() {
+        match *op {
+            StackOp::Push(elem) => list.push(elem),
+            StackOp::TryPush(elem) => list
+                .try_push(elem)
+                .map_err(|err| format!("step {step}: try_push: {err}"))?,
+            StackOp::Pop => {
+                ensure_eq!(list.pop(), model.pop_front(), "step {step}: pop");
+            }
+            StackOp::Bump => {
+                if let Some(elem) = list.peek_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+                if let Some(elem) = model.front_mut() {
+                    *elem = elem.wrapping_add(1);
+                }
+            }
+        }
+        if let StackOp::Push(elem) | StackOp::TryPush(elem) = *op {
+            model.push_front(elem);
+        }
+        ensure_eq!(
+            list.peek(),
+            model.front(),
+            "after step {step} ({op:?}): peek"
+        );
+        ensure_eq!(
+            list.iter().collect::<Vec<_>>(),
+            model.iter().collect::<Vec<_>>(),
+            "after step {step} ({op:?}): elements"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn second() {
+    check("second::List", stack_op, run_second);
+}